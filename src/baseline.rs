@@ -0,0 +1,230 @@
+use crate::diagnostic::Diagnostic;
+use crate::tools::ToolResult;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const BASELINE_FILE: &str = ".claude-reviews-baseline.json";
+const VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BaselineFile {
+    version: u32,
+    findings: Vec<Entry>,
+}
+
+/// One recorded finding. Only `fingerprint` is used for matching; the other
+/// fields keep the file reviewable in diffs.
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    fingerprint: String,
+    tool: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    rule: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    message: String,
+}
+
+/// Known findings keyed by fingerprint, counted so that a second occurrence
+/// of an already-baselined finding is still reported as new.
+#[derive(Debug, Default)]
+pub struct Baseline {
+    counts: HashMap<String, usize>,
+}
+
+/// Collapses whitespace and digit runs so that messages mentioning line
+/// numbers or counts survive unrelated edits.
+fn normalize_message(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut prev_digit = false;
+    for word in message.split_whitespace() {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        for c in word.chars() {
+            if c.is_ascii_digit() {
+                if !prev_digit {
+                    out.push('#');
+                }
+                prev_digit = true;
+            } else {
+                out.push(c);
+                prev_digit = false;
+            }
+        }
+        prev_digit = false;
+    }
+    out
+}
+
+/// FNV-1a, chosen over `DefaultHasher` because its output must stay stable
+/// across Rust releases for committed baseline files.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in bytes {
+        hash ^= u64::from(*b);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Line-independent identity of a finding: tool, rule, file and normalized
+/// message.
+pub fn fingerprint(d: &Diagnostic) -> String {
+    let file = d
+        .file
+        .as_deref()
+        .map(|f| f.strip_prefix("./").unwrap_or(f).to_string_lossy())
+        .unwrap_or_default();
+    let key = format!(
+        "{}\0{}\0{}\0{}",
+        d.tool,
+        d.rule.as_deref().unwrap_or_default(),
+        file,
+        normalize_message(&d.message)
+    );
+    format!("{:016x}", fnv1a(key.as_bytes()))
+}
+
+pub fn path(root: &Path) -> PathBuf {
+    root.join(BASELINE_FILE)
+}
+
+impl Baseline {
+    /// Loads the baseline at `root`. A missing file yields `None`; an
+    /// unreadable one is reported and also yields `None`.
+    pub fn load(root: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path(root)).ok()?;
+        let file: BaselineFile = match serde_json::from_str(&content) {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("reviews: warning: invalid baseline JSON: {}", e);
+                return None;
+            }
+        };
+        let mut counts = HashMap::new();
+        for entry in file.findings {
+            *counts.entry(entry.fingerprint).or_insert(0) += 1;
+        }
+        Some(Self { counts })
+    }
+
+    /// Removes baselined diagnostics from `results`, returning how many were
+    /// hidden. Raw (unparsed) output cannot be fingerprinted and is kept.
+    pub fn filter(&self, results: &mut [ToolResult]) -> usize {
+        let mut remaining = self.counts.clone();
+        let mut hidden = 0;
+        for result in results.iter_mut() {
+            if let Some(diagnostics) = &mut result.diagnostics {
+                diagnostics.retain(|d| match remaining.get_mut(&fingerprint(d)) {
+                    Some(n) if *n > 0 => {
+                        *n -= 1;
+                        hidden += 1;
+                        false
+                    }
+                    _ => true,
+                });
+            }
+        }
+        hidden
+    }
+}
+
+/// Writes every parsed diagnostic in `results` to the baseline at `root`,
+/// sorted for stable diffs. Returns the number of findings recorded.
+pub fn write(root: &Path, results: &[ToolResult]) -> std::io::Result<usize> {
+    let mut findings: Vec<Entry> = results
+        .iter()
+        .filter_map(|r| r.diagnostics.as_ref())
+        .flatten()
+        .map(|d| Entry {
+            fingerprint: fingerprint(d),
+            tool: d.tool.to_string(),
+            rule: d.rule.clone(),
+            file: d.file.as_ref().map(|f| f.to_string_lossy().into_owned()),
+            message: d.message.clone(),
+        })
+        .collect();
+    findings.sort_by(|a, b| {
+        (&a.file, &a.tool, &a.fingerprint).cmp(&(&b.file, &b.tool, &b.fingerprint))
+    });
+    let count = findings.len();
+    let file = BaselineFile {
+        version: VERSION,
+        findings,
+    };
+    let mut json = serde_json::to_string_pretty(&file).map_err(std::io::Error::other)?;
+    json.push('\n');
+    std::fs::write(path(root), json)?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    fn diag(line: u32, message: &str) -> Diagnostic {
+        Diagnostic {
+            tool: "oxlint".into(),
+            rule: Some("eslint(no-unused-vars)".into()),
+            file: Some("src/a.ts".into()),
+            line: Some(line),
+            message: message.into(),
+            ..Default::default()
+        }
+    }
+
+    fn result(diagnostics: Vec<Diagnostic>) -> ToolResult {
+        ToolResult {
+            name: "oxlint".into(),
+            output: String::new(),
+            success: false,
+            diagnostics: Some(diagnostics),
+        }
+    }
+
+    #[test]
+    fn fingerprint_ignores_line_and_numbers() {
+        assert_eq!(
+            fingerprint(&diag(3, "unused at   line 3")),
+            fingerprint(&diag(40, "unused at line 41"))
+        );
+        assert_ne!(
+            fingerprint(&diag(3, "'x' is unused")),
+            fingerprint(&diag(3, "'y' is unused"))
+        );
+    }
+
+    #[test]
+    fn fingerprint_is_stable() {
+        assert_eq!(fingerprint(&diag(1, "'x' is unused")), "f818d32df0dc1d87");
+    }
+
+    #[test]
+    fn write_then_filter_hides_known_findings() {
+        let tmp = TempDir::new("baseline-roundtrip");
+        let count = write(&tmp, &[result(vec![diag(1, "'x' is unused")])]).unwrap();
+        assert_eq!(count, 1);
+
+        let baseline = Baseline::load(&tmp).unwrap();
+        let mut results = vec![result(vec![
+            diag(5, "'x' is unused"),
+            diag(6, "'x' is unused"),
+            diag(7, "'z' is unused"),
+        ])];
+        assert_eq!(baseline.filter(&mut results), 1);
+        let left = results[0].diagnostics.as_ref().unwrap();
+        assert_eq!(left.len(), 2);
+        assert_eq!(left[1].message, "'z' is unused");
+    }
+
+    #[test]
+    fn missing_or_invalid_baseline_is_none() {
+        let tmp = TempDir::new("baseline-missing");
+        assert!(Baseline::load(&tmp).is_none());
+        std::fs::write(path(&tmp), "{not json").unwrap();
+        assert!(Baseline::load(&tmp).is_none());
+    }
+}
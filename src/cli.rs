@@ -0,0 +1,203 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+usage: reviews [--sarif FILE]    read a Claude Code hook event from stdin
+       reviews run [--format markdown|json|sarif] [PATH]
+                                 run all enabled tools and print the results
+       reviews baseline [PATH]   record current findings in .claude-reviews-baseline.json
+       reviews doctor [PATH]     show detected project, config and tool availability";
+
+/// Output format of `reviews run`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    #[default]
+    Markdown,
+    Json,
+    Sarif,
+}
+
+impl Format {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "markdown" | "md" => Ok(Self::Markdown),
+            "json" => Ok(Self::Json),
+            "sarif" => Ok(Self::Sarif),
+            other => Err(format!("unknown format: {other}")),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Default mode: hook JSON arrives on stdin. `sarif` additionally writes
+    /// the reported findings as a SARIF log.
+    Hook {
+        sarif: Option<PathBuf>,
+    },
+    /// Run all enabled tools for the project at `path` without skill matching
+    /// and print the results.
+    Run {
+        format: Format,
+        path: Option<PathBuf>,
+    },
+    /// Run all enabled tools and write the baseline for the project at `path`.
+    Baseline {
+        path: Option<PathBuf>,
+    },
+    /// Report project detection, config location and tool resolution.
+    Doctor {
+        path: Option<PathBuf>,
+    },
+    Help,
+}
+
+/// Parses arguments after the program name.
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    let Some((first, rest)) = args.split_first() else {
+        return Ok(Command::Hook { sarif: None });
+    };
+    match first.as_str() {
+        "-h" | "--help" | "help" => Ok(Command::Help),
+        "run" => parse_run(rest),
+        "baseline" => Ok(Command::Baseline {
+            path: single_path(rest)?,
+        }),
+        "doctor" => Ok(Command::Doctor {
+            path: single_path(rest)?,
+        }),
+        flag if flag.starts_with('-') => parse_hook(args),
+        other => Err(format!("unknown command: {other}")),
+    }
+}
+
+fn parse_hook(args: &[String]) -> Result<Command, String> {
+    let mut sarif = None;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if let Some(value) = arg.strip_prefix("--sarif=") {
+            sarif = Some(PathBuf::from(value));
+        } else if arg == "--sarif" {
+            let value = iter.next().ok_or("--sarif requires a file path")?;
+            sarif = Some(PathBuf::from(value));
+        } else {
+            return Err(format!("unknown option: {arg}"));
+        }
+    }
+    Ok(Command::Hook { sarif })
+}
+
+fn parse_run(args: &[String]) -> Result<Command, String> {
+    let mut format = Format::default();
+    let mut positional = Vec::new();
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if let Some(value) = arg.strip_prefix("--format=") {
+            format = Format::parse(value)?;
+        } else if arg == "--format" {
+            let value = iter.next().ok_or("--format requires a value")?;
+            format = Format::parse(value)?;
+        } else {
+            positional.push(arg.clone());
+        }
+    }
+    Ok(Command::Run {
+        format,
+        path: single_path(&positional)?,
+    })
+}
+
+fn single_path(args: &[String]) -> Result<Option<PathBuf>, String> {
+    match args {
+        [] => Ok(None),
+        [path] if !path.starts_with('-') => Ok(Some(PathBuf::from(path))),
+        [arg] => Err(format!("unknown option: {arg}")),
+        [_, extra, ..] => Err(format!("unexpected argument: {extra}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn no_args_is_hook_mode() {
+        assert_eq!(parse(&[]), Ok(Command::Hook { sarif: None }));
+    }
+
+    #[test]
+    fn hook_mode_with_sarif() {
+        let expected = Ok(Command::Hook {
+            sarif: Some("out.sarif".into()),
+        });
+        assert_eq!(parse(&args(&["--sarif", "out.sarif"])), expected);
+        assert_eq!(parse(&args(&["--sarif=out.sarif"])), expected);
+        assert!(parse(&args(&["--sarif"])).is_err());
+        assert!(parse(&args(&["--verbose"])).is_err());
+    }
+
+    #[test]
+    fn baseline_with_optional_path() {
+        assert_eq!(
+            parse(&args(&["baseline"])),
+            Ok(Command::Baseline { path: None })
+        );
+        assert_eq!(
+            parse(&args(&["baseline", "apps/web"])),
+            Ok(Command::Baseline {
+                path: Some("apps/web".into())
+            })
+        );
+    }
+
+    #[test]
+    fn run_with_format_and_path() {
+        assert_eq!(
+            parse(&args(&["run"])),
+            Ok(Command::Run {
+                format: Format::Markdown,
+                path: None
+            })
+        );
+        assert_eq!(
+            parse(&args(&["run", "--format", "json", "apps/web"])),
+            Ok(Command::Run {
+                format: Format::Json,
+                path: Some("apps/web".into())
+            })
+        );
+        assert_eq!(
+            parse(&args(&["run", "apps/web", "--format=sarif"])),
+            Ok(Command::Run {
+                format: Format::Sarif,
+                path: Some("apps/web".into())
+            })
+        );
+        assert!(parse(&args(&["run", "--format", "xml"])).is_err());
+        assert!(parse(&args(&["run", "--format"])).is_err());
+    }
+
+    #[test]
+    fn doctor_with_optional_path() {
+        assert_eq!(
+            parse(&args(&["doctor"])),
+            Ok(Command::Doctor { path: None })
+        );
+        assert_eq!(
+            parse(&args(&["doctor", "web"])),
+            Ok(Command::Doctor {
+                path: Some("web".into())
+            })
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_input() {
+        assert!(parse(&args(&["frobnicate"])).is_err());
+        assert!(parse(&args(&["baseline", "--nope"])).is_err());
+        assert!(parse(&args(&["baseline", "a", "b"])).is_err());
+    }
+}
@@ -1,43 +1,363 @@
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 const CONFIG_FILE: &str = ".claude-reviews.json";
+/// Uncommitted per-checkout overrides, found next to the project config or,
+/// without one, at the git root.
+const LOCAL_CONFIG_FILE: &str = ".claude-reviews.local.json";
 
 /// Generates three items from a field list:
-/// - `ToolsConfig`: all `bool` fields (default `true`) — runtime config
-/// - `ProjectToolsConfig`: all `Option<bool>` fields — JSON deserialization target
-/// - `ToolsConfig::apply()`: merges `ProjectToolsConfig` overrides into defaults
+/// - `ToolsConfig`: all `ToolSettings` fields (enabled by default) — runtime config
+/// - `ProjectToolsConfig`: all `Option<ProjectToolSettings>` fields — JSON deserialization target
+/// - `ToolsConfig::apply()`: merges `ProjectToolsConfig` overrides into the current values
+///
+/// plus a `ToolsConfig::each_mut()` helper.
 macro_rules! define_tools {
-    ($($field:ident),+ $(,)?) => {
+    (@enabled) => { true };
+    (@enabled $default:expr) => { $default };
+    ($($field:ident $(= $default:expr)?),+ $(,)?) => {
         #[derive(Debug, Clone)]
         pub struct ToolsConfig {
-            $(pub $field: bool,)+
+            $(pub $field: ToolSettings,)+
         }
 
         impl Default for ToolsConfig {
             fn default() -> Self {
-                Self { $($field: true,)+ }
+                Self {
+                    $($field: ToolSettings {
+                        enabled: define_tools!(@enabled $($default)?),
+                        ..ToolSettings::default()
+                    },)+
+                }
             }
         }
 
         #[derive(Debug, Deserialize)]
         struct ProjectToolsConfig {
-            $($field: Option<bool>,)+
+            $($field: Option<ProjectToolSettings>,)+
         }
 
         impl ToolsConfig {
-            fn apply(&mut self, overrides: &ProjectToolsConfig) {
-                $(if let Some(v) = overrides.$field { self.$field = v; })+
+            /// Calls `set` with the name of each overridden tool.
+            fn apply(&mut self, overrides: &ProjectToolsConfig, mut set: impl FnMut(&str)) {
+                $(if let Some(v) = &overrides.$field {
+                    self.$field.apply(v);
+                    set(stringify!($field));
+                })+
+            }
+
+            pub fn each_mut(&mut self, mut f: impl FnMut(&mut ToolSettings)) {
+                $(f(&mut self.$field);)+
             }
         }
     };
 }
 
+/// Runtime settings for one tool.
+#[derive(Debug, Clone)]
+pub struct ToolSettings {
+    pub enabled: bool,
+    /// Overrides the default per-tool timeout.
+    pub timeout: Option<Duration>,
+    /// Appended after the tool's built-in arguments.
+    pub args: Vec<String>,
+    /// Extra environment variables for the tool process.
+    pub env: HashMap<String, String>,
+}
+
+impl Default for ToolSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout: None,
+            args: Vec::new(),
+            env: HashMap::new(),
+        }
+    }
+}
+
+impl ToolSettings {
+    fn apply(&mut self, overrides: &ProjectToolSettings) {
+        match overrides {
+            ProjectToolSettings::Enabled(enabled) => self.enabled = *enabled,
+            ProjectToolSettings::Detailed(detail) => {
+                if let Some(enabled) = detail.enabled {
+                    self.enabled = enabled;
+                }
+                if let Some(ms) = detail.timeout_ms {
+                    self.timeout = Some(Duration::from_millis(ms));
+                }
+                if let Some(args) = &detail.args {
+                    self.args = args.clone();
+                }
+                if let Some(env) = &detail.env {
+                    self.env = env.clone();
+                }
+            }
+        }
+    }
+}
+
+/// A tool entry is either a bare `bool` or an object with overrides.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum ProjectToolSettings {
+    Enabled(bool),
+    Detailed(ProjectToolDetail),
+}
+
+impl ProjectToolSettings {
+    /// The `enabled` value, if this entry sets one.
+    fn enabled(&self) -> Option<bool> {
+        match self {
+            Self::Enabled(enabled) => Some(*enabled),
+            Self::Detailed(detail) => detail.enabled,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectToolDetail {
+    enabled: Option<bool>,
+    timeout_ms: Option<u64>,
+    args: Option<Vec<String>>,
+    env: Option<HashMap<String, String>>,
+}
+
 define_tools! {
     knip,
     oxlint,
     tsgo,
     react_doctor,
+    vue_tsc,
+    svelte_check,
+    astro_check,
+    eslint,
+    biome = false,
+    clippy,
+    cargo_check = false,
+    cargo_test = false,
+    machete,
+    audit = false,
+    ruff,
+    mypy,
+    pyright,
+    go_vet,
+    staticcheck,
+    golangci_lint,
+    tests = false,
+}
+
+/// A user-defined tool from `custom_tools`, run alongside the built-ins.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CustomTool {
+    pub name: String,
+    /// Executable name (resolved like the built-ins) or a path relative to
+    /// the project root.
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Working directory relative to the project root.
+    pub cwd: Option<PathBuf>,
+    #[serde(default)]
+    pub when: Conditions,
+    /// `"raw"` or the name of a built-in tool whose parser reads the output.
+    #[serde(default = "raw_parser")]
+    pub parser: String,
+    #[serde(default, rename = "timeout_ms", deserialize_with = "millis")]
+    pub timeout: Option<Duration>,
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+/// Detection conditions for a custom tool. Empty lists always match.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Conditions {
+    /// Paths relative to the project root; any one existing matches.
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+    /// `package.json` dependency names; any one declared matches.
+    #[serde(default)]
+    pub dependencies: Vec<String>,
+}
+
+fn raw_parser() -> String {
+    "raw".into()
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+fn millis<'de, D>(deserializer: D) -> Result<Option<Duration>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Ok(Option::<u64>::deserialize(deserializer)?.map(Duration::from_millis))
+}
+
+/// Which findings are reported: everything in the project, or only those on
+/// lines changed relative to `base` (working tree, or the index for `Staged`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Scope {
+    #[default]
+    Project,
+    Diff,
+    Staged,
+}
+
+/// Per-skill overrides from `profiles`, applied when that skill triggers the
+/// hook.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Profile {
+    /// Runs exactly these tools: built-in names (`npm-audit`), config keys
+    /// (`audit`) or `custom_tools` names.
+    pub tools: Option<Vec<String>>,
+    /// Replaces every tool's timeout.
+    #[serde(default, rename = "timeout_ms", deserialize_with = "millis")]
+    pub timeout: Option<Duration>,
+    pub scope: Option<Scope>,
+    pub base: Option<String>,
+}
+
+impl Profile {
+    /// Fields set in `overrides` replace this profile's; unset ones are kept.
+    fn apply(&mut self, overrides: Profile) {
+        if overrides.tools.is_some() {
+            self.tools = overrides.tools;
+        }
+        if overrides.timeout.is_some() {
+            self.timeout = overrides.timeout;
+        }
+        if overrides.scope.is_some() {
+            self.scope = overrides.scope;
+        }
+        if overrides.base.is_some() {
+            self.base = overrides.base;
+        }
+    }
+}
+
+/// `PostToolUse` mode: after an `Edit`, `Write` or `MultiEdit`, the
+/// file-capable tools lint just the edited file.
+#[derive(Debug, Clone)]
+pub struct PostToolUse {
+    pub enabled: bool,
+    /// Caps each tool's timeout so an edit never stalls the agent for long.
+    pub timeout: Duration,
+    /// Built-in tool names to run; `None` runs every enabled file-capable
+    /// tool.
+    pub tools: Option<Vec<String>>,
+}
+
+impl Default for PostToolUse {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout: Duration::from_secs(10),
+            tools: None,
+        }
+    }
+}
+
+impl PostToolUse {
+    fn apply(&mut self, overrides: ProjectPostToolUse) {
+        if let Some(enabled) = overrides.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(ms) = overrides.timeout_ms {
+            self.timeout = Duration::from_millis(ms);
+        }
+        if let Some(tools) = overrides.tools {
+            self.tools = Some(tools);
+        }
+    }
+
+    /// Whether the built-in tool `name` is selected for this mode.
+    pub fn runs(&self, name: &str) -> bool {
+        self.tools
+            .as_ref()
+            .is_none_or(|tools| tools.iter().any(|t| t == name))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectPostToolUse {
+    enabled: Option<bool>,
+    timeout_ms: Option<u64>,
+    tools: Option<Vec<String>>,
+}
+
+/// `Stop` mode: keeps the agent working while new errors remain in its
+/// changes. Off by default.
+#[derive(Debug, Clone)]
+pub struct StopGate {
+    pub enabled: bool,
+    /// Blocks per session before the gate gives up and lets the agent stop.
+    pub max_retries: u32,
+}
+
+impl Default for StopGate {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_retries: 3,
+        }
+    }
+}
+
+impl StopGate {
+    fn apply(&mut self, overrides: ProjectStopGate) {
+        if let Some(enabled) = overrides.enabled {
+            self.enabled = enabled;
+        }
+        if let Some(max_retries) = overrides.max_retries {
+            self.max_retries = max_retries;
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectStopGate {
+    enabled: Option<bool>,
+    max_retries: Option<u32>,
+}
+
+/// A config file layer, in increasing precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Layer {
+    /// `$XDG_CONFIG_HOME/claude-reviews/config.json`
+    User,
+    /// `.claude-reviews.json`
+    Project,
+    /// `.claude-reviews.local.json`
+    Local,
+}
+
+impl Layer {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::User => "user",
+            Self::Project => "project",
+            Self::Local => "local",
+        }
+    }
+}
+
+/// How the analysis root is chosen from the working directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RootStrategy {
+    /// The enclosing git repository.
+    #[default]
+    Git,
+    /// The closest ancestor with a `package.json`, within the repository.
+    NearestPackage,
 }
 
 #[derive(Debug, Clone)]
@@ -45,6 +365,35 @@ pub struct Config {
     pub enabled: bool,
     pub skills: Vec<String>,
     pub tools: ToolsConfig,
+    pub scope: Scope,
+    /// Git ref to diff against outside `Scope::Project`; `None` means `HEAD`.
+    pub base: Option<String>,
+    /// Where to write a SARIF log of the reported findings, relative to the
+    /// project root.
+    pub sarif_path: Option<PathBuf>,
+    pub custom_tools: Vec<CustomTool>,
+    pub root: RootStrategy,
+    /// Drop ESLint findings that oxlint also reports for the same rule.
+    pub prefer_oxlint: bool,
+    pub post_tool_use: PostToolUse,
+    pub stop_gate: StopGate,
+    /// Emit the top-level `decision`/`reason`/`additionalContext` fields
+    /// instead of `hookSpecificOutput`, for older Claude Code versions.
+    pub legacy_output: bool,
+    /// Skill name to the overrides used when it runs. Skills with a profile
+    /// trigger the hook even when missing from `skills`.
+    pub profiles: BTreeMap<String, Profile>,
+    /// Absolute paths the findings are limited to; empty means the whole
+    /// project. Set from skill arguments, never from config files.
+    pub paths: Vec<PathBuf>,
+    /// Runs exactly these tools, whether enabled or not. Set from skill
+    /// arguments, never from config files.
+    pub only_tools: Option<Vec<String>>,
+    /// Config files that were loaded, in merge order.
+    pub layers: Vec<(Layer, PathBuf)>,
+    /// Which layer last set each overridden key (e.g. `skills`, `tools.knip`).
+    /// Keys left at their defaults are absent.
+    pub sources: BTreeMap<String, Layer>,
 }
 
 impl Default for Config {
@@ -53,6 +402,20 @@ impl Default for Config {
             enabled: true,
             skills: vec!["review".into()],
             tools: ToolsConfig::default(),
+            scope: Scope::Project,
+            base: None,
+            sarif_path: None,
+            custom_tools: Vec::new(),
+            root: RootStrategy::Git,
+            prefer_oxlint: false,
+            post_tool_use: PostToolUse::default(),
+            stop_gate: StopGate::default(),
+            legacy_output: false,
+            profiles: BTreeMap::new(),
+            paths: Vec::new(),
+            only_tools: None,
+            layers: Vec::new(),
+            sources: BTreeMap::new(),
         }
     }
 }
@@ -62,51 +425,218 @@ struct ProjectConfig {
     enabled: Option<bool>,
     skills: Option<Vec<String>>,
     tools: Option<ProjectToolsConfig>,
+    scope: Option<Scope>,
+    base: Option<String>,
+    sarif_path: Option<PathBuf>,
+    custom_tools: Option<Vec<CustomTool>>,
+    root: Option<RootStrategy>,
+    prefer_oxlint: Option<bool>,
+    post_tool_use: Option<ProjectPostToolUse>,
+    stop_gate: Option<ProjectStopGate>,
+    legacy_output: Option<bool>,
+    profiles: Option<BTreeMap<String, Profile>>,
+}
+
+/// `$XDG_CONFIG_HOME/claude-reviews/config.json`, falling back to
+/// `~/.config` when `XDG_CONFIG_HOME` is unset or relative.
+fn user_config_path() -> Option<PathBuf> {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .filter(|p| p.is_absolute())
+        .or_else(|| std::env::var_os("HOME").map(|h| PathBuf::from(h).join(".config")))?;
+    Some(base.join("claude-reviews").join("config.json"))
 }
 
 impl Config {
-    pub fn load(start: &Path) -> Self {
-        let default = Self::default();
-        let Some(config_path) = Self::find_config(start) else {
-            return default;
+    /// Whether a tool known by any of `names` runs, given its `enabled`
+    /// setting.
+    pub fn runs(&self, names: &[&str], enabled: bool) -> bool {
+        match &self.only_tools {
+            Some(only) => only.iter().any(|n| names.contains(&n.as_str())),
+            None => enabled,
+        }
+    }
+
+    /// Names in `only_tools` that match no built-in tool name, config key or
+    /// custom tool, e.g. typos in a profile or `--tools`.
+    pub fn unknown_tools(&self) -> Vec<&str> {
+        let known = |name: &str| {
+            crate::tools::TOOLS
+                .iter()
+                .any(|t| t.name == name || t.key == name)
+                || self.custom_tools.iter().any(|t| t.name == name)
+        };
+        let only = self.only_tools.iter().flatten();
+        only.map(String::as_str).filter(|n| !known(n)).collect()
+    }
+
+    /// Whether invoking `skill` triggers the hook.
+    pub fn triggers(&self, skill: &str) -> bool {
+        self.skills.iter().any(|s| s == skill) || self.profiles.contains_key(skill)
+    }
+
+    /// Applies the profile for `skill`, if any.
+    pub fn apply_profile(&mut self, skill: &str) {
+        let Some(profile) = self.profiles.get(skill).cloned() else {
+            return;
         };
-        Self::load_from(&config_path, default)
+        if profile.tools.is_some() {
+            self.only_tools = profile.tools;
+        }
+        if let Some(timeout) = profile.timeout {
+            self.tools.each_mut(|t| t.timeout = Some(timeout));
+            for tool in &mut self.custom_tools {
+                tool.timeout = Some(timeout);
+            }
+        }
+        if let Some(scope) = profile.scope {
+            self.scope = scope;
+        }
+        if profile.base.is_some() {
+            self.base = profile.base;
+        }
     }
 
-    fn find_config(start: &Path) -> Option<PathBuf> {
+    /// Merges the user config, the project config and the local override, in
+    /// that order, over the defaults.
+    pub fn load(start: &Path) -> Self {
+        Self::load_layers(start, user_config_path())
+    }
+
+    fn load_layers(start: &Path, user: Option<PathBuf>) -> Self {
+        let project = Self::find_file(start, CONFIG_FILE);
+        let local = Self::local_file(start, project.as_deref());
+        let layers = [
+            (Layer::User, user.filter(|p| p.is_file())),
+            (Layer::Project, project),
+            (Layer::Local, local),
+        ];
+        let mut config = Self::default();
+        let mut biome_chosen = false;
+        let mut cargo_check_chosen = false;
+        for (layer, path) in layers {
+            let Some(path) = path else { continue };
+            if let Some(parsed) = Self::read(&path) {
+                if let Some(tools) = &parsed.tools {
+                    let chosen = |t: &Option<ProjectToolSettings>| {
+                        t.as_ref().and_then(ProjectToolSettings::enabled).is_some()
+                    };
+                    biome_chosen |= chosen(&tools.biome);
+                    cargo_check_chosen |= chosen(&tools.cargo_check);
+                }
+                config = config.merge(parsed, layer);
+                config.layers.push((layer, path));
+            }
+        }
+        // Biome and oxlint overlap, so Biome only runs by default in place of
+        // oxlint, unless a layer enables or disables it explicitly.
+        if !biome_chosen {
+            config.tools.biome.enabled = !config.tools.oxlint.enabled;
+        }
+        // clippy already reports every rustc diagnostic, so cargo check only
+        // runs by default when clippy is off.
+        if !cargo_check_chosen {
+            config.tools.cargo_check.enabled = !config.tools.clippy.enabled;
+        }
+        config
+    }
+
+    fn find_file(start: &Path, name: &str) -> Option<PathBuf> {
         crate::traverse::walk_ancestors(start, |dir| {
-            let candidate = dir.join(CONFIG_FILE);
+            let candidate = dir.join(name);
             candidate.exists().then_some(candidate)
         })
     }
 
-    fn load_from(path: &Path, default: Config) -> Config {
+    /// The local override beside `project`, so one in a parent directory
+    /// never lands on top of a nested project config.
+    fn local_file(start: &Path, project: Option<&Path>) -> Option<PathBuf> {
+        let dir = match project {
+            Some(project) => project.parent()?.to_path_buf(),
+            None => crate::traverse::walk_ancestors(start, |dir| {
+                dir.join(".git").exists().then(|| dir.to_path_buf())
+            })?,
+        };
+        Some(dir.join(LOCAL_CONFIG_FILE)).filter(|p| p.is_file())
+    }
+
+    fn read(path: &Path) -> Option<ProjectConfig> {
         let content = match std::fs::read_to_string(path) {
             Ok(c) => c,
             Err(e) => {
                 eprintln!("reviews: warning: failed to read config: {}", e);
-                return default;
+                return None;
             }
         };
-        let project: ProjectConfig = match serde_json::from_str(&content) {
-            Ok(p) => p,
+        match serde_json::from_str(&content) {
+            Ok(p) => Some(p),
             Err(e) => {
-                eprintln!("reviews: warning: invalid config JSON: {}", e);
-                return default;
+                eprintln!(
+                    "reviews: warning: invalid config JSON in {}: {}",
+                    path.display(),
+                    e
+                );
+                None
             }
-        };
-        default.merge(project)
+        }
     }
 
-    fn merge(mut self, project: ProjectConfig) -> Self {
+    fn merge(mut self, project: ProjectConfig, layer: Layer) -> Self {
+        let sources = &mut self.sources;
+        let mut set = |key: &str| {
+            sources.insert(key.to_string(), layer);
+        };
         if let Some(enabled) = project.enabled {
             self.enabled = enabled;
+            set("enabled");
         }
         if let Some(skills) = project.skills {
             self.skills = skills;
+            set("skills");
         }
         if let Some(ref tools) = project.tools {
-            self.tools.apply(tools);
+            self.tools
+                .apply(tools, |tool| set(&format!("tools.{tool}")));
+        }
+        if let Some(scope) = project.scope {
+            self.scope = scope;
+            set("scope");
+        }
+        if let Some(base) = project.base {
+            self.base = Some(base);
+            set("base");
+        }
+        if let Some(sarif_path) = project.sarif_path {
+            self.sarif_path = Some(sarif_path);
+            set("sarif_path");
+        }
+        if let Some(custom_tools) = project.custom_tools {
+            self.custom_tools = custom_tools;
+            set("custom_tools");
+        }
+        if let Some(root) = project.root {
+            self.root = root;
+            set("root");
+        }
+        if let Some(prefer_oxlint) = project.prefer_oxlint {
+            self.prefer_oxlint = prefer_oxlint;
+            set("prefer_oxlint");
+        }
+        if let Some(post_tool_use) = project.post_tool_use {
+            self.post_tool_use.apply(post_tool_use);
+            set("post_tool_use");
+        }
+        if let Some(stop_gate) = project.stop_gate {
+            self.stop_gate.apply(stop_gate);
+            set("stop_gate");
+        }
+        if let Some(legacy_output) = project.legacy_output {
+            self.legacy_output = legacy_output;
+            set("legacy_output");
+        }
+        for (skill, profile) in project.profiles.into_iter().flatten() {
+            set(&format!("profiles.{skill}"));
+            self.profiles.entry(skill).or_default().apply(profile);
         }
         self
     }
@@ -125,10 +655,34 @@ mod tests {
 
         let config = Config::load(&tmp);
         assert!(config.enabled);
-        assert!(config.tools.knip);
-        assert!(config.tools.oxlint);
-        assert!(config.tools.tsgo);
-        assert!(config.tools.react_doctor);
+        assert!(config.tools.knip.enabled);
+        assert!(config.tools.oxlint.enabled);
+        assert!(config.tools.tsgo.enabled);
+        assert!(config.tools.react_doctor.enabled);
+        assert!(config.tools.clippy.enabled);
+        assert!(config.tools.machete.enabled);
+        assert!(!config.tools.audit.enabled);
+    }
+
+    #[test]
+    fn cargo_check_replaces_clippy_and_cargo_test_is_opt_in() {
+        let tmp = TempDir::new("config-cargo");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"tools": {"cargo_test": {"timeout_ms": 300000}}}"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&tmp);
+        assert!(!config.tools.cargo_check.enabled);
+        assert!(!config.tools.cargo_test.enabled);
+
+        fs::write(tmp.join(CONFIG_FILE), r#"{"tools": {"clippy": false}}"#).unwrap();
+        assert!(Config::load(&tmp).tools.cargo_check.enabled);
+
+        fs::write(tmp.join(CONFIG_FILE), r#"{"tools": {"cargo_test": true}}"#).unwrap();
+        assert!(Config::load(&tmp).tools.cargo_test.enabled);
     }
 
     #[test]
@@ -139,10 +693,10 @@ mod tests {
 
         let config = Config::load(&tmp);
         assert!(config.enabled);
-        assert!(!config.tools.knip);
-        assert!(config.tools.oxlint);
-        assert!(config.tools.tsgo);
-        assert!(config.tools.react_doctor);
+        assert!(!config.tools.knip.enabled);
+        assert!(config.tools.oxlint.enabled);
+        assert!(config.tools.tsgo.enabled);
+        assert!(config.tools.react_doctor.enabled);
     }
 
     #[test]
@@ -163,7 +717,7 @@ mod tests {
 
         let config = Config::load(&tmp);
         assert!(config.enabled);
-        assert!(config.tools.knip);
+        assert!(config.tools.knip.enabled);
     }
 
     #[test]
@@ -198,6 +752,320 @@ mod tests {
         fs::create_dir_all(&subdir).unwrap();
 
         let config = Config::load(&subdir);
-        assert!(!config.tools.knip);
+        assert!(!config.tools.knip.enabled);
+    }
+
+    #[test]
+    fn tool_object_form_sets_overrides() {
+        let tmp = TempDir::new("config-tool-object");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"tools": {"tsgo": {"timeout_ms": 20000, "args": ["-p", "tsconfig.app.json"], "env": {"NODE_OPTIONS": "--max-old-space-size=4096"}}, "knip": {"enabled": false}}}"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&tmp);
+        let tsgo = &config.tools.tsgo;
+        assert!(tsgo.enabled);
+        assert_eq!(tsgo.timeout, Some(Duration::from_secs(20)));
+        assert_eq!(tsgo.args, vec!["-p", "tsconfig.app.json"]);
+        assert_eq!(
+            tsgo.env.get("NODE_OPTIONS").map(String::as_str),
+            Some("--max-old-space-size=4096")
+        );
+        assert!(!config.tools.knip.enabled);
+        assert!(config.tools.oxlint.args.is_empty());
+    }
+
+    #[test]
+    fn layers_merge_user_project_local() {
+        let tmp = TempDir::new("config-layers");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        let user = tmp.join("user.json");
+        fs::write(
+            &user,
+            r#"{"skills": ["review", "audit"], "tools": {"react_doctor": false, "knip": false}}"#,
+        )
+        .unwrap();
+        fs::write(tmp.join(CONFIG_FILE), r#"{"tools": {"knip": true}}"#).unwrap();
+        fs::write(tmp.join(LOCAL_CONFIG_FILE), r#"{"scope": "diff"}"#).unwrap();
+
+        let config = Config::load_layers(&tmp, Some(user.clone()));
+        assert_eq!(config.skills, vec!["review", "audit"]);
+        assert!(!config.tools.react_doctor.enabled);
+        assert!(config.tools.knip.enabled);
+        assert_eq!(config.scope, Scope::Diff);
+
+        assert_eq!(config.sources["skills"], Layer::User);
+        assert_eq!(config.sources["tools.react_doctor"], Layer::User);
+        assert_eq!(config.sources["tools.knip"], Layer::Project);
+        assert_eq!(config.sources["scope"], Layer::Local);
+        assert!(!config.sources.contains_key("enabled"));
+        let layers: Vec<_> = config.layers.iter().map(|(l, _)| *l).collect();
+        assert_eq!(layers, vec![Layer::User, Layer::Project, Layer::Local]);
+        assert_eq!(config.layers[0].1, user);
+    }
+
+    #[test]
+    fn local_file_sits_beside_the_project_config() {
+        let tmp = TempDir::new("config-local-dir");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::create_dir_all(tmp.join("app/src")).unwrap();
+        fs::write(tmp.join(LOCAL_CONFIG_FILE), r#"{"scope": "diff"}"#).unwrap();
+        fs::write(tmp.join("app").join(CONFIG_FILE), "{}").unwrap();
+
+        let config = Config::load_layers(&tmp.join("app/src"), None);
+        assert_eq!(config.scope, Scope::Project);
+        let layers: Vec<_> = config.layers.iter().map(|(l, _)| *l).collect();
+        assert_eq!(layers, vec![Layer::Project]);
+
+        // Without a project config, the local file is read from the git root.
+        fs::remove_file(tmp.join("app").join(CONFIG_FILE)).unwrap();
+        let config = Config::load_layers(&tmp.join("app/src"), None);
+        assert_eq!(config.scope, Scope::Diff);
+    }
+
+    #[test]
+    fn invalid_layer_is_skipped() {
+        let tmp = TempDir::new("config-layer-invalid");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        let user = tmp.join("user.json");
+        fs::write(&user, r#"{"skills": ["audit"]}"#).unwrap();
+        fs::write(tmp.join(CONFIG_FILE), "not json").unwrap();
+
+        let config = Config::load_layers(&tmp, Some(user));
+        assert_eq!(config.skills, vec!["audit"]);
+        assert_eq!(config.layers.len(), 1);
+    }
+
+    #[test]
+    fn default_scope_is_project() {
+        let tmp = TempDir::new("config-scope-default");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+
+        let config = Config::load(&tmp);
+        assert_eq!(config.scope, Scope::Project);
+        assert!(config.base.is_none());
+    }
+
+    #[test]
+    fn diff_scope_with_base() {
+        let tmp = TempDir::new("config-scope-diff");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"scope": "diff", "base": "origin/main"}"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&tmp);
+        assert_eq!(config.scope, Scope::Diff);
+        assert_eq!(config.base.as_deref(), Some("origin/main"));
+    }
+
+    #[test]
+    fn staged_scope() {
+        let tmp = TempDir::new("config-scope-staged");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join(CONFIG_FILE), r#"{"scope": "staged"}"#).unwrap();
+
+        let config = Config::load(&tmp);
+        assert_eq!(config.scope, Scope::Staged);
+    }
+
+    #[test]
+    fn sarif_path_from_config() {
+        let tmp = TempDir::new("config-sarif");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"sarif_path": "out/reviews.sarif"}"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&tmp);
+        assert_eq!(config.sarif_path, Some(PathBuf::from("out/reviews.sarif")));
+    }
+
+    #[test]
+    fn root_strategy_from_config() {
+        let tmp = TempDir::new("config-root");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        assert_eq!(Config::load(&tmp).root, RootStrategy::Git);
+
+        fs::write(tmp.join(CONFIG_FILE), r#"{"root": "nearest-package"}"#).unwrap();
+        assert_eq!(Config::load(&tmp).root, RootStrategy::NearestPackage);
+    }
+
+    #[test]
+    fn biome_defaults_to_off_while_oxlint_runs() {
+        let tmp = TempDir::new("config-biome");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        assert!(!Config::load(&tmp).tools.biome.enabled);
+
+        fs::write(tmp.join(CONFIG_FILE), r#"{"tools": {"oxlint": false}}"#).unwrap();
+        assert!(Config::load(&tmp).tools.biome.enabled);
+
+        fs::write(tmp.join(CONFIG_FILE), r#"{"tools": {"biome": true}}"#).unwrap();
+        let config = Config::load(&tmp);
+        assert!(config.tools.biome.enabled && config.tools.oxlint.enabled);
+
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"tools": {"biome": {"timeout_ms": 5000}}}"#,
+        )
+        .unwrap();
+        let config = Config::load(&tmp);
+        assert!(!config.tools.biome.enabled);
+        assert_eq!(
+            config.tools.biome.timeout,
+            Some(Duration::from_millis(5000))
+        );
+    }
+
+    #[test]
+    fn prefer_oxlint_from_config() {
+        let tmp = TempDir::new("config-prefer-oxlint");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        assert!(!Config::load(&tmp).prefer_oxlint);
+
+        fs::write(tmp.join(CONFIG_FILE), r#"{"prefer_oxlint": true}"#).unwrap();
+        let config = Config::load(&tmp);
+        assert!(config.prefer_oxlint);
+        assert_eq!(config.sources.get("prefer_oxlint"), Some(&Layer::Project));
+    }
+
+    #[test]
+    fn post_tool_use_merges_field_by_field() {
+        let tmp = TempDir::new("config-post-tool-use");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        let config = Config::load(&tmp);
+        assert!(config.post_tool_use.enabled);
+        assert_eq!(config.post_tool_use.timeout, Duration::from_secs(10));
+        assert!(config.post_tool_use.runs("eslint"));
+
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"post_tool_use": {"timeout_ms": 3000, "tools": ["oxlint", "ruff"]}}"#,
+        )
+        .unwrap();
+        fs::write(
+            tmp.join(LOCAL_CONFIG_FILE),
+            r#"{"post_tool_use": {"enabled": false}}"#,
+        )
+        .unwrap();
+        let config = Config::load(&tmp);
+        assert!(!config.post_tool_use.enabled);
+        assert_eq!(config.post_tool_use.timeout, Duration::from_millis(3000));
+        assert!(config.post_tool_use.runs("ruff"));
+        assert!(!config.post_tool_use.runs("eslint"));
+        assert_eq!(config.sources.get("post_tool_use"), Some(&Layer::Local));
+    }
+
+    #[test]
+    fn profiles_trigger_and_override_per_skill() {
+        let tmp = TempDir::new("config-profiles");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{
+                "custom_tools": [{"name": "gitleaks", "command": "gitleaks"}],
+                "profiles": {
+                    "review": {"tools": ["oxlint", "tsgo"], "scope": "diff"},
+                    "security-review": {"tools": ["audit", "gitleaks"], "timeout_ms": 120000}
+                }
+            }"#,
+        )
+        .unwrap();
+        fs::write(
+            tmp.join(LOCAL_CONFIG_FILE),
+            r#"{"profiles": {"review": {"base": "main"}}}"#,
+        )
+        .unwrap();
+        let config = Config::load(&tmp);
+        assert!(config.triggers("review"));
+        assert!(config.triggers("security-review"));
+        assert!(!config.triggers("audit"));
+        assert_eq!(config.sources.get("profiles.review"), Some(&Layer::Local));
+
+        // A later layer overrides only the fields it sets.
+        let mut review = config.clone();
+        review.apply_profile("review");
+        let expected = vec!["oxlint".to_string(), "tsgo".to_string()];
+        assert_eq!(review.only_tools, Some(expected));
+        assert_eq!(review.scope, Scope::Diff);
+        assert_eq!(review.base.as_deref(), Some("main"));
+
+        let mut security = config.clone();
+        security.apply_profile("security-review");
+        assert!(security.unknown_tools().is_empty());
+        security.only_tools = Some(vec!["oxlnit".into(), "npm-audit".into()]);
+        assert_eq!(security.unknown_tools(), vec!["oxlnit"]);
+        security.apply_profile("security-review");
+        assert!(security.runs(&["npm-audit", "audit"], false));
+        assert!(security.runs(&["gitleaks"], false));
+        assert!(!security.runs(&["oxlint", "oxlint"], true));
+        assert_eq!(security.tools.tsgo.timeout, Some(Duration::from_secs(120)));
+        let gitleaks = &security.custom_tools[0];
+        assert_eq!(gitleaks.timeout, Some(Duration::from_secs(120)));
+    }
+
+    #[test]
+    fn tool_keys_name_their_settings() {
+        for tool in crate::tools::TOOLS {
+            let json = format!(r#"{{"{}": {{"timeout_ms": 1}}}}"#, tool.key);
+            let overrides: ProjectToolsConfig = serde_json::from_str(&json).unwrap();
+            let mut tools = ToolsConfig::default();
+            tools.apply(&overrides, |_| {});
+            let timeout = (tool.settings)(&tools).timeout;
+            assert_eq!(timeout, Some(Duration::from_millis(1)), "{}", tool.key);
+        }
+    }
+
+    #[test]
+    fn stop_gate_is_opt_in() {
+        let tmp = TempDir::new("config-stop-gate");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        let config = Config::load(&tmp);
+        assert!(!config.stop_gate.enabled);
+        assert_eq!(config.stop_gate.max_retries, 3);
+
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"stop_gate": {"enabled": true, "max_retries": 1}}"#,
+        )
+        .unwrap();
+        let config = Config::load(&tmp);
+        assert!(config.stop_gate.enabled);
+        assert_eq!(config.stop_gate.max_retries, 1);
+    }
+
+    #[test]
+    fn custom_tools_from_config() {
+        let tmp = TempDir::new("config-custom");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join(CONFIG_FILE),
+            r#"{"custom_tools": [
+                {"name": "eslint", "command": "eslint", "args": ["-f", "json", "."],
+                 "cwd": "web", "when": {"dependencies": ["eslint"]}, "timeout_ms": 5000},
+                {"name": "codemods", "command": "./scripts/check-codemods.sh", "enabled": false}
+            ]}"#,
+        )
+        .unwrap();
+
+        let config = Config::load(&tmp);
+        let [eslint, codemods] = config.custom_tools.as_slice() else {
+            panic!("expected two custom tools");
+        };
+        assert_eq!(eslint.args, vec!["-f", "json", "."]);
+        assert_eq!(eslint.cwd, Some(PathBuf::from("web")));
+        assert_eq!(eslint.when.dependencies, vec!["eslint"]);
+        assert!(eslint.when.files.is_empty());
+        assert_eq!(eslint.parser, "raw");
+        assert_eq!(eslint.timeout, Some(Duration::from_secs(5)));
+        assert!(eslint.enabled);
+        assert!(!codemods.enabled);
     }
 }
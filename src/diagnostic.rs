@@ -0,0 +1,201 @@
+use serde::Serialize;
+use std::borrow::Cow;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Parses a tool's primary output stream into diagnostics.
+/// Returns `None` when the output is not in the expected format, so callers
+/// can fall back to the raw text.
+pub type Parser = fn(&str) -> Option<Vec<Diagnostic>>;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    #[default]
+    Warning,
+    Info,
+}
+
+impl Severity {
+    /// Maps the severity labels used by the supported tools onto the shared scale.
+    pub fn from_label(label: &str) -> Self {
+        match label.to_ascii_lowercase().as_str() {
+            "error" | "fatal" | "deny" | "critical" | "high" => Self::Error,
+            "warning" | "warn" | "moderate" | "medium" => Self::Warning,
+            _ => Self::Info,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Error => "error",
+            Self::Warning => "warning",
+            Self::Info => "info",
+        }
+    }
+}
+
+/// A single finding reported by a tool, normalized across tools.
+///
+/// `file` is relative to `ProjectInfo::root` whenever the tool reports a path
+/// inside the project. Lines and columns are 1-based.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub tool: Cow<'static, str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule: Option<String>,
+    pub severity: Severity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_column: Option<u32>,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl Diagnostic {
+    /// Rewrites absolute paths under `root` to root-relative paths.
+    pub fn relativize(&mut self, root: &Path) {
+        if let Some(file) = &self.file
+            && let Ok(rel) = file.strip_prefix(root)
+        {
+            self.file = Some(rel.to_path_buf());
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{}", file.display())?;
+            if let Some(line) = self.line {
+                write!(f, ":{line}")?;
+                if let Some(column) = self.column {
+                    write!(f, ":{column}")?;
+                }
+            }
+            write!(f, ": ")?;
+        }
+        write!(f, "{}", self.severity.as_str())?;
+        if let Some(rule) = &self.rule {
+            write!(f, "[{rule}]")?;
+        }
+        write!(f, " {}", self.message)?;
+        if let Some(fix) = &self.fix {
+            write!(f, " (fix: {fix})")?;
+        }
+        Ok(())
+    }
+}
+
+/// One-line count summary such as `2 errors, 1 warning`.
+pub fn summarize(diagnostics: &[Diagnostic]) -> String {
+    let mut parts = Vec::new();
+    for severity in [Severity::Error, Severity::Warning, Severity::Info] {
+        let count = diagnostics
+            .iter()
+            .filter(|d| d.severity == severity)
+            .count();
+        if count > 0 {
+            let plural = if count == 1 { "" } else { "s" };
+            parts.push(format!("{count} {}{plural}", severity.as_str()));
+        }
+    }
+    parts.join(", ")
+}
+
+/// Reads a JSON number field as a 1-based position.
+pub fn json_u32(value: &serde_json::Value, key: &str) -> Option<u32> {
+    value
+        .get(key)
+        .and_then(|v| v.as_u64())
+        .and_then(|n| u32::try_from(n).ok())
+}
+
+/// Reads a JSON string field as an owned `String`.
+pub fn json_string(value: &serde_json::Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn severity_from_label() {
+        assert_eq!(Severity::from_label("ERROR"), Severity::Error);
+        assert_eq!(Severity::from_label("warn"), Severity::Warning);
+        assert_eq!(Severity::from_label("advice"), Severity::Info);
+    }
+
+    #[test]
+    fn display_full_location() {
+        let d = Diagnostic {
+            rule: Some("TS2322".into()),
+            severity: Severity::Error,
+            file: Some("src/a.ts".into()),
+            line: Some(3),
+            column: Some(7),
+            message: "Type mismatch".into(),
+            fix: Some("cast it".into()),
+            ..Default::default()
+        };
+        assert_eq!(
+            d.to_string(),
+            "src/a.ts:3:7: error[TS2322] Type mismatch (fix: cast it)"
+        );
+    }
+
+    #[test]
+    fn display_without_location() {
+        let d = Diagnostic {
+            message: "global problem".into(),
+            ..Default::default()
+        };
+        assert_eq!(d.to_string(), "warning global problem");
+    }
+
+    #[test]
+    fn relativize_strips_root() {
+        let mut d = Diagnostic {
+            file: Some("/repo/src/a.ts".into()),
+            ..Default::default()
+        };
+        d.relativize(Path::new("/repo"));
+        assert_eq!(d.file.as_deref(), Some(Path::new("src/a.ts")));
+    }
+
+    #[test]
+    fn relativize_keeps_outside_paths() {
+        let mut d = Diagnostic {
+            file: Some("/elsewhere/a.ts".into()),
+            ..Default::default()
+        };
+        d.relativize(Path::new("/repo"));
+        assert_eq!(d.file.as_deref(), Some(Path::new("/elsewhere/a.ts")));
+    }
+
+    #[test]
+    fn summarize_counts_by_severity() {
+        let diags = vec![
+            Diagnostic {
+                severity: Severity::Error,
+                ..Default::default()
+            },
+            Diagnostic {
+                severity: Severity::Error,
+                ..Default::default()
+            },
+            Diagnostic::default(),
+        ];
+        assert_eq!(summarize(&diags), "2 errors, 1 warning");
+    }
+}
@@ -0,0 +1,375 @@
+use crate::diagnostic::Diagnostic;
+use crate::tools::ToolResult;
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Files and added/modified line ranges between a base revision and the
+/// working tree. Paths are relative to the directory the diff was taken in.
+#[derive(Debug, Default)]
+pub struct DiffScope {
+    /// An empty range list means the whole file is new (e.g. untracked).
+    files: HashMap<PathBuf, Vec<RangeInclusive<u32>>>,
+}
+
+fn git(root: &Path, args: &[&str]) -> Option<String> {
+    let output = match Command::new("git")
+        .args(["-c", "core.quotePath=false"])
+        .args(args)
+        .current_dir(root)
+        .output()
+    {
+        Ok(o) => o,
+        Err(e) => {
+            eprintln!("reviews: git spawn error: {}", e);
+            return None;
+        }
+    };
+    if !output.status.success() {
+        eprintln!(
+            "reviews: warning: git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+impl DiffScope {
+    /// Diffs `root` against the merge base with `base`, or against `HEAD`
+    /// when no base is given. With `staged`, only the index is compared;
+    /// otherwise the working tree is, and untracked files count as entirely
+    /// changed. Returns `None` when git cannot produce a diff.
+    pub fn compute(root: &Path, staged: bool, base: Option<&str>) -> Option<Self> {
+        let rev = match base {
+            Some(base) => git(root, &["merge-base", base, "HEAD"])?.trim().to_string(),
+            None => "HEAD".to_string(),
+        };
+        let mut args = vec![
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--unified=0",
+            "--relative",
+        ];
+        if staged {
+            args.push("--cached");
+        }
+        args.push(&rev);
+        let diff = git(root, &args)?;
+        let mut scope = Self::parse_unified(&diff);
+
+        if !staged
+            && let Some(untracked) =
+                git(root, &["ls-files", "-z", "--others", "--exclude-standard"])
+        {
+            for file in untracked.split('\0').filter(|f| !f.is_empty()) {
+                scope.files.insert(PathBuf::from(file), Vec::new());
+            }
+        }
+        Some(scope)
+    }
+
+    fn parse_unified(diff: &str) -> Self {
+        let mut files: HashMap<PathBuf, Vec<RangeInclusive<u32>>> = HashMap::new();
+        let mut current: Option<PathBuf> = None;
+        // Old and new lines left in the current hunk, so added lines that
+        // start with `++ ` are not mistaken for file headers.
+        let mut remaining: (u32, u32) = (0, 0);
+
+        for line in diff.lines() {
+            if remaining != (0, 0) {
+                let (old, new) = remaining;
+                remaining = match line.as_bytes().first() {
+                    Some(b'-') => (old.saturating_sub(1), new),
+                    Some(b'+') => (old, new.saturating_sub(1)),
+                    Some(b' ') => (old.saturating_sub(1), new.saturating_sub(1)),
+                    Some(b'\\') => (old, new),
+                    // Malformed hunk: treat the line as a header.
+                    _ => (0, 0),
+                };
+                if remaining != (0, 0) || line.starts_with(['-', '+', ' ', '\\']) {
+                    continue;
+                }
+            }
+            if let Some(path) = line.strip_prefix("+++ ") {
+                current = Self::new_path(path);
+                if let Some(file) = &current {
+                    files.entry(file.clone()).or_default();
+                }
+            } else if let Some(hunk) = line.strip_prefix("@@ ")
+                && let Some(file) = &current
+            {
+                let count = |side| Self::hunk_side(hunk, side).map_or(0, |(_, count)| count);
+                remaining = (count('-'), count('+'));
+                if let Some(range) = Self::parse_hunk(hunk) {
+                    files.entry(file.clone()).or_default().push(range);
+                }
+            }
+        }
+
+        // A modified file whose only hunks are deletions has no lines to keep.
+        files.retain(|_, ranges| !ranges.is_empty());
+        Self { files }
+    }
+
+    /// The path in a `+++ b/...` header. Git appends a tab to names containing
+    /// spaces and C-quotes names with special characters, e.g. `"b/a\"b.ts"`.
+    fn new_path(header: &str) -> Option<PathBuf> {
+        let header = header.strip_suffix('\t').unwrap_or(header);
+        let path = match header.strip_prefix('"') {
+            Some(quoted) => unquote(quoted.strip_suffix('"')?)?,
+            None => header.to_string(),
+        };
+        path.strip_prefix("b/").map(PathBuf::from)
+    }
+
+    /// Parses the new-side range of `-a,b +c,d @@`. Pure deletions yield `None`.
+    fn parse_hunk(hunk: &str) -> Option<RangeInclusive<u32>> {
+        let (start, count) = Self::hunk_side(hunk, '+')?;
+        (count > 0).then(|| start..=start + count - 1)
+    }
+
+    /// The `(start, count)` after `side` (`-` or `+`) in a hunk header.
+    fn hunk_side(hunk: &str, side: char) -> Option<(u32, u32)> {
+        let range = hunk.split_whitespace().find_map(|p| p.strip_prefix(side))?;
+        let mut parts = range.splitn(2, ',');
+        let start: u32 = parts.next()?.parse().ok()?;
+        let count: u32 = match parts.next() {
+            Some(c) => c.parse().ok()?,
+            None => 1,
+        };
+        Some((start, count))
+    }
+
+    /// Changed files in sorted order.
+    pub fn files(&self) -> Vec<PathBuf> {
+        let mut files: Vec<_> = self.files.keys().cloned().collect();
+        files.sort();
+        files
+    }
+
+    /// Whether `diagnostic` falls inside the diff. Findings without a file
+    /// are kept, as are file-level findings on changed files.
+    pub fn contains(&self, diagnostic: &Diagnostic) -> bool {
+        let Some(file) = &diagnostic.file else {
+            return true;
+        };
+        let file = file.strip_prefix("./").unwrap_or(file);
+        let Some(ranges) = self.files.get(file) else {
+            return false;
+        };
+        match diagnostic.line {
+            Some(line) if !ranges.is_empty() => ranges.iter().any(|r| r.contains(&line)),
+            _ => true,
+        }
+    }
+
+    /// Drops parsed diagnostics outside the diff. Raw (unparsed) output is
+    /// left untouched. Returns the number of diagnostics removed.
+    pub fn filter(&self, results: &mut [ToolResult]) -> usize {
+        let mut hidden = 0;
+        for result in results.iter_mut() {
+            if let Some(diagnostics) = &mut result.diagnostics {
+                let before = diagnostics.len();
+                diagnostics.retain(|d| self.contains(d));
+                hidden += before - diagnostics.len();
+            }
+        }
+        hidden
+    }
+}
+
+/// Undoes git's C-style quoting: backslash escapes and octal-escaped bytes.
+fn unquote(quoted: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(quoted.len());
+    let mut iter = quoted.bytes();
+    while let Some(b) = iter.next() {
+        if b != b'\\' {
+            bytes.push(b);
+            continue;
+        }
+        let escaped = match iter.next()? {
+            b'a' => 0x07,
+            b'b' => 0x08,
+            b'f' => 0x0c,
+            b'n' => b'\n',
+            b'r' => b'\r',
+            b't' => b'\t',
+            b'v' => 0x0b,
+            d @ b'0'..=b'3' => {
+                let mut value = d - b'0';
+                for _ in 0..2 {
+                    let d = iter.next().filter(u8::is_ascii_digit)?;
+                    value = value * 8 + (d - b'0');
+                }
+                value
+            }
+            other => other,
+        };
+        bytes.push(escaped);
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::fs;
+
+    fn diag(file: &str, line: Option<u32>) -> Diagnostic {
+        Diagnostic {
+            file: Some(file.into()),
+            line,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parses_hunks_per_file() {
+        let diff = "\
+diff --git a/src/a.ts b/src/a.ts
+--- a/src/a.ts
++++ b/src/a.ts
+@@ -3,0 +4,2 @@ fn
++new
++new
+@@ -10 +12 @@
+-old
++new
+diff --git a/gone.ts b/gone.ts
+--- a/gone.ts
++++ /dev/null
+@@ -1,3 +0,0 @@
+";
+        let scope = DiffScope::parse_unified(diff);
+        assert!(scope.contains(&diag("src/a.ts", Some(4))));
+        assert!(scope.contains(&diag("src/a.ts", Some(5))));
+        assert!(!scope.contains(&diag("src/a.ts", Some(6))));
+        assert!(scope.contains(&diag("src/a.ts", Some(12))));
+        assert!(!scope.contains(&diag("gone.ts", Some(1))));
+    }
+
+    #[test]
+    fn added_lines_that_look_like_headers_stay_in_the_hunk() {
+        let diff = "\
+diff --git a/notes.md b/notes.md
+--- a/notes.md
++++ b/notes.md
+@@ -1,2 +1,3 @@
+-old
++++ b/fake.ts
++new
+ kept
+@@ -9,0 +10 @@
++later
+";
+        let scope = DiffScope::parse_unified(diff);
+        assert_eq!(scope.files(), vec![PathBuf::from("notes.md")]);
+        assert!(scope.contains(&diag("notes.md", Some(10))));
+    }
+
+    #[test]
+    fn strips_tab_after_names_with_spaces() {
+        let scope = DiffScope::parse_unified("+++ b/my file.ts\t\n@@ -1 +1 @@\n");
+        assert_eq!(scope.files(), vec![PathBuf::from("my file.ts")]);
+    }
+
+    #[test]
+    fn unquotes_c_style_paths() {
+        let diff = "+++ \"b/say \\\"hi\\\".ts\"\n@@ -1 +1 @@\n-a\n+b\n+++ \"b/caf\\303\\251\\tx.ts\"\n@@ -1 +1 @@\n-a\n+b\n";
+        assert_eq!(
+            DiffScope::parse_unified(diff).files(),
+            vec![
+                PathBuf::from("caf\u{e9}\tx.ts"),
+                PathBuf::from("say \"hi\".ts")
+            ]
+        );
+    }
+
+    #[test]
+    fn deletion_only_hunks_drop_file() {
+        let diff = "+++ b/a.ts\n@@ -5,2 +4,0 @@\n";
+        assert!(DiffScope::parse_unified(diff).files().is_empty());
+    }
+
+    #[test]
+    fn keeps_fileless_and_file_level_findings() {
+        let scope = DiffScope::parse_unified("+++ b/a.ts\n@@ -1 +1 @@\n");
+        assert!(scope.contains(&Diagnostic::default()));
+        assert!(scope.contains(&diag("a.ts", None)));
+        assert!(scope.contains(&diag("./a.ts", Some(1))));
+        assert!(!scope.contains(&diag("b.ts", None)));
+    }
+
+    #[test]
+    fn filter_counts_hidden_and_keeps_raw() {
+        let scope = DiffScope::parse_unified("+++ b/a.ts\n@@ -1 +1 @@\n");
+        let mut results = vec![
+            ToolResult {
+                name: "oxlint".into(),
+                output: String::new(),
+                success: true,
+                diagnostics: Some(vec![diag("a.ts", Some(1)), diag("a.ts", Some(9))]),
+            },
+            ToolResult {
+                name: "react-doctor".into(),
+                output: "raw".into(),
+                success: true,
+                diagnostics: None,
+            },
+        ];
+        assert_eq!(scope.filter(&mut results), 1);
+        assert_eq!(results[0].diagnostics.as_ref().unwrap().len(), 1);
+        assert_eq!(results[1].output, "raw");
+    }
+
+    #[test]
+    fn computes_scope_from_git() {
+        let tmp = TempDir::new("diff-git");
+        let git = |args: &[&str]| {
+            let ok = Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(&*tmp)
+                .output()
+                .unwrap()
+                .status
+                .success();
+            assert!(ok, "git {args:?} failed");
+        };
+        git(&["init", "-q"]);
+        fs::write(tmp.join("a.ts"), "one\ntwo\nthree\n").unwrap();
+        fs::write(tmp.join("my file.ts"), "one\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "init"]);
+        fs::write(tmp.join("a.ts"), "one\nTWO\nthree\n").unwrap();
+        fs::write(tmp.join("my file.ts"), "ONE\n").unwrap();
+        fs::write(tmp.join("new.ts"), "fresh\n").unwrap();
+
+        let scope = DiffScope::compute(&tmp, false, None).unwrap();
+        assert!(scope.contains(&diag("a.ts", Some(2))));
+        assert!(!scope.contains(&diag("a.ts", Some(1))));
+        assert!(scope.contains(&diag("my file.ts", Some(1))));
+        assert!(scope.contains(&diag("new.ts", Some(1))));
+        assert_eq!(
+            scope.files(),
+            vec![
+                PathBuf::from("a.ts"),
+                PathBuf::from("my file.ts"),
+                PathBuf::from("new.ts")
+            ]
+        );
+
+        let staged = DiffScope::compute(&tmp, true, None).unwrap();
+        assert!(staged.files().is_empty());
+        git(&["add", "a.ts"]);
+        let staged = DiffScope::compute(&tmp, true, None).unwrap();
+        assert_eq!(staged.files(), vec![PathBuf::from("a.ts")]);
+
+        assert!(DiffScope::compute(&tmp, false, Some("no-such-ref")).is_none());
+    }
+}
@@ -0,0 +1,269 @@
+use crate::config::Config;
+use crate::project::ProjectInfo;
+use crate::resolve::{self, Resolution};
+use crate::tools;
+use std::fmt::Write;
+use std::path::Path;
+
+fn yes_no(flag: bool) -> &'static str {
+    if flag { "yes" } else { "no" }
+}
+
+/// Detected UI frameworks besides React, e.g. `vue, next`.
+fn frameworks(project: &ProjectInfo) -> String {
+    let detected: Vec<&str> = [
+        ("vue", project.has_vue),
+        ("svelte", project.has_svelte),
+        ("astro", project.has_astro),
+        ("next", project.has_next),
+    ]
+    .into_iter()
+    .filter_map(|(name, found)| found.then_some(name))
+    .collect();
+    if detected.is_empty() {
+        "none".into()
+    } else {
+        detected.join(", ")
+    }
+}
+
+/// Writes one tool line. Returns `false` when the tool would run but its
+/// binary cannot be found. `--version` is only probed for tools that would run.
+fn row(out: &mut String, name: &str, enabled: bool, applies: bool, resolution: Resolution) -> bool {
+    let mut status = match (enabled, applies) {
+        (false, _) => "disabled",
+        (true, false) => "n/a",
+        (true, true) => "ok",
+    };
+    let detail = match resolution {
+        Resolution::Local(bin) | Resolution::Path(bin) if status != "ok" => {
+            bin.display().to_string()
+        }
+        Resolution::Local(bin) | Resolution::Path(bin) => {
+            let version = tools::version(name, &bin);
+            let version = version.unwrap_or_else(|| "version unknown".into());
+            format!("{} ({})", bin.display(), version)
+        }
+        Resolution::Missing => {
+            if status == "ok" {
+                status = "missing";
+            }
+            "not found in project bin directories or $PATH".to_string()
+        }
+    };
+    let _ = writeln!(out, "  {:<14}{:<10}{}", name, status, detail);
+    status != "missing"
+}
+
+/// Renders the `reviews doctor` report for `dir`. The flag is `false` when an
+/// enabled tool that applies to the project cannot be found.
+pub fn report(dir: &Path) -> (String, bool) {
+    report_with(dir, resolve::locate)
+}
+
+/// `report` with the binary lookup injected, so tests do not depend on what
+/// is installed.
+fn report_with(dir: &Path, locate: impl Fn(&str, &Path) -> Resolution) -> (String, bool) {
+    let config = Config::load(dir);
+    let project = ProjectInfo::detect(dir, config.root);
+    let mut out = String::new();
+    let mut healthy = true;
+
+    let _ = writeln!(out, "project: {}", project.root.display());
+    let _ = writeln!(out, "  package.json:  {}", yes_no(project.has_package_json));
+    let _ = writeln!(out, "  tsconfig.json: {}", yes_no(project.has_tsconfig));
+    let _ = writeln!(out, "  react:         {}", yes_no(project.has_react));
+    let _ = writeln!(out, "  frameworks:    {}", frameworks(&project));
+    let _ = writeln!(
+        out,
+        "  eslint config: {}",
+        yes_no(project.has_eslint_config)
+    );
+    let _ = writeln!(out, "  biome config:  {}", yes_no(project.has_biome_config));
+    let _ = writeln!(out, "  Cargo.toml:    {}", yes_no(project.has_cargo_toml));
+    let _ = writeln!(out, "  python:        {}", yes_no(project.has_python));
+    let _ = writeln!(out, "  go.mod:        {}", yes_no(project.has_go_mod));
+    if !project.packages.is_empty() {
+        let _ = writeln!(out, "  packages:");
+        for package in &project.packages {
+            let info = &package.info;
+            let _ = writeln!(
+                out,
+                "    {} ({}): tsconfig.json {}, react {}, frameworks {}",
+                package.name,
+                package.dir.display(),
+                yes_no(info.has_tsconfig),
+                yes_no(info.has_react),
+                frameworks(info)
+            );
+        }
+    }
+    if config.layers.is_empty() {
+        let _ = writeln!(out, "config: none (using defaults)");
+    } else {
+        let _ = writeln!(out, "config:");
+        for (layer, path) in &config.layers {
+            let _ = writeln!(out, "  {:<9}{}", layer.as_str(), path.display());
+        }
+        for (key, layer) in &config.sources {
+            let _ = writeln!(out, "  {} set by {}", key, layer.as_str());
+        }
+    }
+
+    let _ = writeln!(out, "\ntools:");
+    for tool in tools::TOOLS {
+        let settings = (tool.settings)(&config.tools);
+        let enabled = config.runs(&[tool.name, tool.key], settings.enabled);
+        // Per-package tools run in each workspace package they apply to, so
+        // resolve them from the first such package.
+        let root = if tool.per_package && !project.packages.is_empty() {
+            project
+                .packages
+                .iter()
+                .find(|p| (tool.applies)(&p.info))
+                .map(|p| &p.info)
+        } else {
+            Some(&project).filter(|p| (tool.applies)(p))
+        };
+        let applies = root.is_some();
+        let start = root.map_or(&project.root, |p| &p.root);
+        let resolution = locate(tool.bin, start);
+        healthy &= row(&mut out, tool.name, enabled, applies, resolution);
+    }
+    for tool in &config.custom_tools {
+        let applies = tools::custom::applies(tool, &project);
+        let resolution = if tool.command.contains('/') {
+            let bin = tools::custom::bin(tool, &project);
+            if bin.is_file() {
+                Resolution::Path(bin)
+            } else {
+                Resolution::Missing
+            }
+        } else {
+            locate(&tool.command, &project.root)
+        };
+        let enabled = config.runs(&[&tool.name], tool.enabled);
+        healthy &= row(&mut out, &tool.name, enabled, applies, resolution);
+    }
+
+    (out, healthy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn fake_bin(root: &Path, name: &str) {
+        let bin_dir = root.join("node_modules/.bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        let path = bin_dir.join(name);
+        fs::write(&path, "#!/bin/sh\necho 9.9.9\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn reports_resolved_tools_with_versions() {
+        let tmp = TempDir::new("doctor-ok");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("package.json"), "{}").unwrap();
+        fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"tools": {"oxlint": false}}"#,
+        )
+        .unwrap();
+        fake_bin(&tmp, "knip");
+        fake_bin(&tmp, "oxlint");
+
+        let (out, healthy) = report(&tmp);
+        assert!(healthy, "{out}");
+        assert!(out.contains("package.json:  yes"));
+        assert!(out.contains("project  "), "{out}");
+        assert!(out.contains("tools.oxlint set by project"), "{out}");
+        let knip = out
+            .lines()
+            .find(|l| l.trim_start().starts_with("knip"))
+            .unwrap();
+        assert!(knip.contains("ok") && knip.contains("(9.9.9)"), "{knip}");
+        let oxlint = out
+            .lines()
+            .find(|l| l.trim_start().starts_with("oxlint"))
+            .unwrap();
+        assert!(oxlint.contains("disabled"), "{oxlint}");
+    }
+
+    #[test]
+    fn per_package_tools_apply_in_workspace_packages() {
+        let tmp = TempDir::new("doctor-workspace");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("package.json"), r#"{"workspaces": ["apps/*"]}"#).unwrap();
+        fs::create_dir_all(tmp.join("apps/web")).unwrap();
+        fs::write(tmp.join("apps/web/package.json"), r#"{"name": "web"}"#).unwrap();
+        fs::write(tmp.join("apps/web/tsconfig.json"), "{}").unwrap();
+        fake_bin(&tmp, "tsgo");
+        fake_bin(&tmp, "oxlint");
+        fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"tools": {"oxlint": false}}"#,
+        )
+        .unwrap();
+
+        let (out, _) = report(&tmp);
+        let line = |name: &str| {
+            out.lines()
+                .find(|l| l.trim_start().starts_with(name))
+                .unwrap()
+                .to_string()
+        };
+        let tsgo = line("tsgo");
+        assert!(tsgo.contains("ok") && tsgo.contains("(9.9.9)"), "{tsgo}");
+        let oxlint = line("oxlint");
+        assert!(
+            oxlint.contains("disabled") && !oxlint.contains("9.9.9"),
+            "{oxlint}"
+        );
+    }
+
+    #[test]
+    fn missing_custom_tool_is_unhealthy() {
+        let tmp = TempDir::new("doctor-custom");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"tools": {"knip": false, "oxlint": false, "tsgo": false, "react_doctor": false},
+                "custom_tools": [{"name": "check", "command": "nonexistent-command-12345"}]}"#,
+        )
+        .unwrap();
+
+        let (out, healthy) = report(&tmp);
+        assert!(!healthy);
+        let check = out
+            .lines()
+            .find(|l| l.trim_start().starts_with("check"))
+            .unwrap();
+        assert!(check.contains("missing"), "{check}");
+    }
+
+    #[test]
+    fn missing_enabled_tool_is_unhealthy() {
+        let tmp = TempDir::new("doctor-missing");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("tsconfig.json"), "{}").unwrap();
+        fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"tools": {"knip": false, "oxlint": false, "react_doctor": false}}"#,
+        )
+        .unwrap();
+
+        let (out, healthy) = report_with(&tmp, |_, _| Resolution::Missing);
+        assert!(!healthy);
+        let tsgo = out
+            .lines()
+            .find(|l| l.trim_start().starts_with("tsgo"))
+            .unwrap();
+        assert!(tsgo.contains("missing"), "{tsgo}");
+        assert!(out.contains("config:"));
+    }
+}
@@ -0,0 +1,205 @@
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::tools::{MAX_OUTPUT_SIZE, ToolResult};
+use std::path::{Path, PathBuf};
+
+/// Error-severity findings in a file that remain after diff scope and
+/// baseline filtering. Warnings, unparsed output and project-wide findings
+/// without a file (e.g. a broken `tsconfig.json`) never block, since the
+/// diff cannot tell whether the session caused them.
+pub fn errors(results: &[ToolResult]) -> Vec<&Diagnostic> {
+    results
+        .iter()
+        .flat_map(|r| r.diagnostics.iter().flatten())
+        .filter(|d| d.severity == Severity::Error && d.file.is_some())
+        .collect()
+}
+
+/// The `reason` fed back to the agent when the gate blocks. `attempt` is
+/// `None` when blocks are not being counted. The list is cut off at the
+/// per-tool output cap.
+pub fn message(errors: &[&Diagnostic], attempt: Option<u32>, max_retries: u32) -> String {
+    let attempt = attempt.map_or(String::new(), |n| format!(" (attempt {n}/{max_retries})"));
+    let mut reason = format!(
+        "Quality gate: {} new {}{}. Fix them before finishing:\n",
+        errors.len(),
+        if errors.len() == 1 {
+            "error remains"
+        } else {
+            "errors remain"
+        },
+        attempt
+    );
+    for (i, d) in errors.iter().enumerate() {
+        let line = format!("- {d}\n");
+        if reason.len() + line.len() > MAX_OUTPUT_SIZE {
+            let omitted = errors.len() - i;
+            reason.push_str(&format!(
+                "- {omitted} more errors omitted: output budget exceeded\n"
+            ));
+            break;
+        }
+        reason.push_str(&line);
+    }
+    reason.trim_end().to_string()
+}
+
+/// How many times the gate has blocked in one session, kept in the
+/// repository's git directory so it survives across hook invocations.
+pub struct Attempts {
+    path: PathBuf,
+}
+
+impl Attempts {
+    /// `None` without a session ID or a git directory above `root`; blocks
+    /// are then not counted.
+    pub fn new(root: &Path, session: Option<&str>) -> Option<Self> {
+        Some(Self::in_dir(&git_dir(root)?, session?))
+    }
+
+    fn in_dir(dir: &Path, session: &str) -> Self {
+        // Session IDs come from stdin; keep only characters safe in a file name.
+        let session: String = session
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric() || *c == '-' || *c == '_')
+            .collect();
+        Self {
+            path: dir.join(format!("claude-reviews-stop-{session}")),
+        }
+    }
+
+    /// Records one more block and returns the new count.
+    pub fn increment(&self) -> u32 {
+        let count = std::fs::read_to_string(&self.path)
+            .ok()
+            .and_then(|s| s.trim().parse::<u32>().ok())
+            .unwrap_or(0)
+            + 1;
+        if let Err(e) = std::fs::write(&self.path, count.to_string()) {
+            eprintln!(
+                "reviews: warning: failed to write {}: {}",
+                self.path.display(),
+                e
+            );
+        }
+        count
+    }
+
+    /// Forgets the session's count once the gate passes.
+    pub fn reset(&self) {
+        let _ = std::fs::remove_file(&self.path);
+    }
+}
+
+/// The `.git` directory above `start`, following the `gitdir:` pointer of a
+/// worktree's `.git` file.
+fn git_dir(start: &Path) -> Option<PathBuf> {
+    crate::traverse::walk_ancestors(start, |dir| {
+        let dot_git = dir.join(".git");
+        if dot_git.is_dir() {
+            return Some(dot_git);
+        }
+        let pointer = std::fs::read_to_string(&dot_git).ok()?;
+        let target = pointer.strip_prefix("gitdir:")?.trim();
+        Some(dir.join(target))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+
+    fn result(diagnostics: Vec<Diagnostic>) -> ToolResult {
+        ToolResult {
+            name: "oxlint".into(),
+            output: String::new(),
+            success: false,
+            diagnostics: Some(diagnostics),
+        }
+    }
+
+    #[test]
+    fn only_errors_block() {
+        let results = vec![
+            result(vec![
+                Diagnostic {
+                    severity: Severity::Error,
+                    file: Some("src/a.ts".into()),
+                    line: Some(3),
+                    message: "bad".into(),
+                    ..Default::default()
+                },
+                Diagnostic {
+                    severity: Severity::Warning,
+                    message: "meh".into(),
+                    ..Default::default()
+                },
+            ]),
+            result(vec![Diagnostic {
+                severity: Severity::Error,
+                message: "Cannot read file tsconfig.json".into(),
+                ..Default::default()
+            }]),
+            ToolResult::skipped("tsgo"),
+        ];
+        let errors = errors(&results);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(
+            message(&errors, Some(2), 3),
+            "Quality gate: 1 new error remains (attempt 2/3). Fix them before finishing:\n\
+             - src/a.ts:3: error bad"
+        );
+        assert!(message(&errors, None, 3).starts_with("Quality gate: 1 new error remains. Fix"));
+    }
+
+    #[test]
+    fn message_is_capped() {
+        let error = Diagnostic {
+            severity: Severity::Error,
+            file: Some("src/a.ts".into()),
+            message: "x".repeat(1000),
+            ..Default::default()
+        };
+        let errors = vec![&error; 500];
+        let reason = message(&errors, None, 3);
+        assert!(reason.len() <= MAX_OUTPUT_SIZE + 100);
+        assert!(reason.ends_with("more errors omitted: output budget exceeded"));
+    }
+
+    #[test]
+    fn attempts_count_per_session_until_reset() {
+        let tmp = TempDir::new("gate-attempts");
+        let attempts = Attempts::in_dir(&tmp, "abc-123");
+        assert_eq!(attempts.increment(), 1);
+        assert_eq!(attempts.increment(), 2);
+        assert_eq!(Attempts::in_dir(&tmp, "other").increment(), 1);
+
+        attempts.reset();
+        assert_eq!(attempts.increment(), 1);
+    }
+
+    #[test]
+    fn attempts_live_in_the_git_dir() {
+        let tmp = TempDir::new("gate-git-dir");
+        std::fs::create_dir_all(tmp.join(".git")).unwrap();
+        std::fs::create_dir_all(tmp.join("pkg")).unwrap();
+        let attempts = Attempts::new(&tmp.join("pkg"), Some("abc")).unwrap();
+        assert_eq!(attempts.path, tmp.join(".git/claude-reviews-stop-abc"));
+        assert!(Attempts::new(&tmp, None).is_none());
+
+        let worktree = TempDir::new("gate-worktree");
+        std::fs::write(worktree.join(".git"), "gitdir: /repo/.git/worktrees/wt\n").unwrap();
+        let attempts = Attempts::new(&worktree, Some("abc")).unwrap();
+        assert_eq!(
+            attempts.path,
+            Path::new("/repo/.git/worktrees/wt/claude-reviews-stop-abc")
+        );
+    }
+
+    #[test]
+    fn session_id_cannot_escape_git_dir() {
+        let tmp = TempDir::new("gate-escape");
+        let attempts = Attempts::in_dir(&tmp, "../../etc/passwd");
+        assert_eq!(attempts.path, tmp.join("claude-reviews-stop-etcpasswd"));
+    }
+}
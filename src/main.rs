@@ -1,111 +1,467 @@
+mod baseline;
+mod cli;
 mod config;
+mod diagnostic;
+mod diff;
+mod doctor;
+mod gate;
+mod output;
 mod project;
+mod report;
 mod resolve;
 mod sanitize;
+mod sarif;
+mod skill_args;
 #[cfg(test)]
 mod test_utils;
 mod tools;
 mod traverse;
+mod workspace;
 
 use serde::Deserialize;
+use std::borrow::Cow;
 use std::io::{self, Read};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
 use std::sync::LazyLock;
+use std::thread::JoinHandle;
 
 static DEBUG: LazyLock<bool> = LazyLock::new(|| std::env::var("REVIEWS_DEBUG").is_ok());
 
 const MAX_INPUT_SIZE: usize = 10_000_000;
 
+/// Tools whose `PostToolUse` event triggers a lint of the edited file.
+const EDIT_TOOLS: &[&str] = &["Edit", "Write", "MultiEdit"];
+
 #[derive(Deserialize)]
 struct HookInput {
-    tool_input: SkillInput,
+    #[serde(default)]
+    hook_event_name: String,
+    session_id: Option<String>,
+    #[serde(default)]
+    tool_name: String,
+    /// Absent for events that are not about a tool call, such as `Stop`.
+    #[serde(default)]
+    tool_input: ToolInput,
 }
 
-#[derive(Deserialize)]
-struct SkillInput {
+/// The fields read from either a `Skill` call or a file edit.
+#[derive(Default, Deserialize)]
+struct ToolInput {
     skill: Option<String>,
+    /// Arguments typed after the skill name, e.g. `src --tools oxlint`.
+    args: Option<String>,
+    file_path: Option<PathBuf>,
 }
 
-fn parse_skill_name(input: &str) -> Option<String> {
-    let hook: HookInput = serde_json::from_str(input).ok()?;
-    hook.tool_input.skill
+fn parse_input(input: &str) -> Option<HookInput> {
+    serde_json::from_str(input).ok()
+}
+
+fn build_output(results: &[tools::ToolResult], baselined: usize, legacy: bool) -> Option<String> {
+    let report = report::render(results, baselined)?;
+    let output = output::pre_tool_use(&report.reason, &report.context, legacy);
+
+    Some(output.to_string())
 }
 
-fn build_output(results: &[tools::ToolResult]) -> Option<String> {
-    if results.is_empty() {
+/// Feedback for an edit: blocks with the findings so the agent fixes them
+/// right away. `None` when the file is clean.
+fn build_post_edit_output(
+    results: &[tools::ToolResult],
+    baselined: usize,
+    legacy: bool,
+) -> Option<String> {
+    if !results.iter().any(report::has_findings) {
         return None;
     }
+    let report = report::render(results, baselined)?;
+    let output = output::post_tool_use(&report.reason, &report.context, legacy);
 
-    let with_output: Vec<_> = results.iter().filter(|r| !r.output.is_empty()).collect();
+    Some(output.to_string())
+}
 
-    let mut context = String::from("# Pre-flight Analysis Results\n\n");
-    for result in &with_output {
-        context.push_str(&format!(
-            "## {}\n\n``````\n{}\n``````\n\n",
-            result.name, result.output
-        ));
+/// `sarif` (from `--sarif`, relative to `cwd`) overrides the configured
+/// `sarif_path` (relative to the project root).
+fn run(input: &str, cwd: &Path, sarif: Option<&Path>) -> Option<String> {
+    let hook = parse_input(input)?;
+    let mut config = config::Config::load(cwd);
+
+    if *DEBUG {
+        eprintln!(
+            "reviews: debug: event={}, tool={}, skill={:?}, enabled={}, skills={:?}",
+            hook.hook_event_name,
+            hook.tool_name,
+            hook.tool_input.skill,
+            config.enabled,
+            config.skills
+        );
+        for (layer, path) in &config.layers {
+            eprintln!(
+                "reviews: debug: {} config {}",
+                layer.as_str(),
+                path.display()
+            );
+        }
+        for (key, layer) in &config.sources {
+            eprintln!("reviews: debug: {key} set by {} config", layer.as_str());
+        }
     }
 
-    let with_issues = with_output.iter().filter(|r| !r.success).count();
-    let reason = if with_issues > 0 {
-        format!(
-            "Pre-flight: {}/{} tools reported ({} with issues)",
-            with_output.len(),
-            results.len(),
-            with_issues
-        )
-    } else {
-        format!(
-            "Pre-flight: {}/{} tools reported",
-            with_output.len(),
-            results.len()
-        )
+    if !config.enabled {
+        return None;
+    }
+    let is_edit = EDIT_TOOLS.contains(&hook.tool_name.as_str());
+    match output::Event::parse(&hook.hook_event_name, is_edit)? {
+        output::Event::Stop => {
+            return run_stop(&config, cwd, hook.session_id.as_deref());
+        }
+        output::Event::PostToolUse if is_edit => {
+            return run_post_edit(&config, cwd, &hook.tool_input.file_path?);
+        }
+        output::Event::PostToolUse => return None,
+        output::Event::SessionStart => return run_session_start(&config, cwd),
+        output::Event::PreToolUse => {}
+    }
+    let skill = hook.tool_input.skill?;
+    if !config.triggers(&skill) {
+        return None;
+    }
+    config.apply_profile(&skill);
+    if let Some(args) = &hook.tool_input.args {
+        let args = skill_args::SkillArgs::parse(args);
+        if *DEBUG {
+            eprintln!("reviews: debug: skill args {args:?}");
+        }
+        args.apply(&mut config, cwd);
+    }
+    for name in config.unknown_tools() {
+        eprintln!("reviews: warning: unknown tool {name} in the tool list, ignoring");
+    }
+
+    let start = std::time::Instant::now();
+    let mut analysis = analyze(&config, cwd);
+
+    let sarif_path = match sarif {
+        Some(path) => Some(cwd.join(path)),
+        None => config.sarif_path.as_ref().map(|p| analysis.root.join(p)),
     };
-    let output = serde_json::json!({
-        "decision": "approve",
-        "reason": reason,
-        "additionalContext": context.trim_end()
-    });
+    if let Some(path) = sarif_path {
+        write_sarif(&path, &analysis);
+    }
+    tools::enforce_total_budget(&mut analysis.results);
 
-    Some(output.to_string())
+    if *DEBUG {
+        eprintln!("reviews: debug: completed in {}ms", start.elapsed().as_millis());
+    }
+
+    build_output(&analysis.results, analysis.baselined, config.legacy_output)
+}
+
+/// `SessionStart` mode: runs every enabled tool so the agent starts with the
+/// project's current findings. Silent when there are none.
+fn run_session_start(config: &config::Config, cwd: &Path) -> Option<String> {
+    let mut analysis = analyze(config, cwd);
+    if !analysis.results.iter().any(report::has_findings) {
+        return None;
+    }
+    tools::enforce_total_budget(&mut analysis.results);
+    let report = report::render(&analysis.results, analysis.baselined)?;
+    Some(output::session_start(&report.context).to_string())
 }
 
-fn run(input: &str, cwd: &Path) -> Option<String> {
-    let skill = parse_skill_name(input)?;
-    let config = config::Config::load(cwd);
+/// `PostToolUse` mode: lints the file an edit just touched with the
+/// file-capable tools. Diff scope and baseline apply as in skill mode.
+fn run_post_edit(config: &config::Config, cwd: &Path, file: &Path) -> Option<String> {
+    if !config.post_tool_use.enabled {
+        return None;
+    }
+    let project = project::ProjectInfo::detect(cwd, config.root);
+    let path = cwd.join(file);
+    let Ok(relative) = path.strip_prefix(&project.root) else {
+        if *DEBUG {
+            eprintln!("reviews: debug: {} is outside the project", file.display());
+        }
+        return None;
+    };
 
-    if *DEBUG {
-        eprintln!("reviews: debug: skill={skill}, enabled={}, skills={:?}", config.enabled, config.skills);
+    let scope = diff_scope(config, &project);
+    let mut ctx = tools::ToolContext::new(project);
+    ctx.files = Some(vec![relative.to_path_buf()]);
+    let mut results = run_file_tools(config, &ctx);
+    if config.prefer_oxlint {
+        tools::eslint::drop_oxlint_duplicates(&mut results);
     }
+    if let Some(scope) = &scope {
+        scope.filter(&mut results);
+    }
+    let baselined =
+        baseline::Baseline::load(&ctx.project.root).map_or(0, |b| b.filter(&mut results));
+    tools::enforce_total_budget(&mut results);
 
-    if !config.enabled || !config.skills.contains(&skill) {
+    build_post_edit_output(&results, baselined, config.legacy_output)
+}
+
+/// `Stop` mode: runs every enabled tool on the files changed since `base`
+/// (the uncommitted changes without one) and blocks while error-severity
+/// findings that are neither baselined nor outside the diff remain. Lets the agent stop once `max_retries` blocks have been spent.
+fn run_stop(config: &config::Config, cwd: &Path, session: Option<&str>) -> Option<String> {
+    if !config.stop_gate.enabled {
+        return None;
+    }
+    let mut config = config.clone();
+    if config.scope == config::Scope::Project {
+        config.scope = config::Scope::Diff;
+    }
+    let analysis = analyze(&config, cwd);
+    if !analysis.scoped {
+        // Without a diff every pre-existing error would block; fail open.
         return None;
     }
 
-    let project = project::ProjectInfo::detect(cwd);
+    // Without a session ID there is no per-session key, so blocks are not
+    // capped.
+    let attempts = gate::Attempts::new(&analysis.root, session);
+    let errors = gate::errors(&analysis.results);
+    if errors.is_empty() {
+        if let Some(attempts) = &attempts {
+            attempts.reset();
+        }
+        return None;
+    }
+    let attempt = attempts.as_ref().map(gate::Attempts::increment);
+    let max_retries = config.stop_gate.max_retries;
+    if let Some(attempts) = &attempts
+        && attempt > Some(max_retries)
+    {
+        eprintln!(
+            "reviews: warning: {} errors remain after {max_retries} retries, allowing stop",
+            errors.len()
+        );
+        attempts.reset();
+        return None;
+    }
+
+    let output = output::stop(&gate::message(&errors, attempt, max_retries));
+    Some(output.to_string())
+}
+
+/// Results of one analysis pass, after diff scope and baseline filtering.
+struct Analysis {
+    root: PathBuf,
+    results: Vec<tools::ToolResult>,
+    baselined: usize,
+    /// Whether a diff scope was applied.
+    scoped: bool,
+}
+
+/// Detects the project at `dir` and runs every enabled tool. Shared by hook
+/// mode and `reviews run` so both report the same findings.
+fn analyze(config: &config::Config, dir: &Path) -> Analysis {
+    let project = project::ProjectInfo::detect(dir, config.root);
 
     if *DEBUG {
         eprintln!(
-            "reviews: debug: root={}, pkg={}, ts={}, react={}",
+            "reviews: debug: root={}, pkg={}, ts={}, react={}, cargo={}, python={}, go={}, packages={}",
             project.root.display(),
             project.has_package_json,
             project.has_tsconfig,
-            project.has_react
+            project.has_react,
+            project.has_cargo_toml,
+            project.has_python,
+            project.has_go_mod,
+            project.packages.len()
         );
     }
 
-    let start = std::time::Instant::now();
-    let mut results = run_tools_parallel(&config, &project);
-    tools::enforce_total_budget(&mut results);
+    let scope = diff_scope(config, &project);
+    let mut paths = Vec::new();
+    for path in &config.paths {
+        match path.strip_prefix(&project.root) {
+            Ok(relative) => paths.push(relative.to_path_buf()),
+            Err(_) => eprintln!(
+                "reviews: warning: ignoring {}: outside the project",
+                path.display()
+            ),
+        }
+    }
+    let mut ctx = tools::ToolContext::new(project);
+    ctx.files = scope.as_ref().map(diff::DiffScope::files);
+    if let Some(files) = &mut ctx.files
+        && !paths.is_empty()
+    {
+        files.retain(|f| paths.iter().any(|p| f.starts_with(p)));
+    }
+    let mut results = run_tools_parallel(config, &ctx, &paths);
+    if config.prefer_oxlint {
+        let dropped = tools::eslint::drop_oxlint_duplicates(&mut results);
+        if *DEBUG {
+            eprintln!("reviews: debug: {dropped} ESLint findings also reported by oxlint dropped");
+        }
+    }
 
-    if *DEBUG {
-        eprintln!("reviews: debug: completed in {}ms", start.elapsed().as_millis());
+    if let Some(scope) = &scope {
+        let hidden = scope.filter(&mut results);
+        if *DEBUG {
+            eprintln!("reviews: debug: {hidden} findings outside diff hidden");
+        }
+    }
+    if !paths.is_empty() {
+        let hidden = skill_args::filter_paths(&mut results, &paths);
+        if *DEBUG {
+            eprintln!("reviews: debug: {hidden} findings outside requested paths hidden");
+        }
     }
+    let baselined =
+        baseline::Baseline::load(&ctx.project.root).map_or(0, |b| b.filter(&mut results));
 
-    build_output(&results)
+    Analysis {
+        root: ctx.project.root,
+        results,
+        baselined,
+        scoped: scope.is_some(),
+    }
 }
 
-fn main() {
+fn write_sarif(path: &Path, analysis: &Analysis) {
+    if let Err(e) = sarif::write(path, &analysis.results, &analysis.root) {
+        eprintln!(
+            "reviews: warning: failed to write SARIF to {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+/// `reviews run`: the hook analysis without skill matching, printed for a
+/// terminal or CI. The configured `sarif_path` is still honored.
+fn run_cli(dir: &Path, format: cli::Format) -> ExitCode {
+    let config = config::Config::load(dir);
+    let analysis = analyze(&config, dir);
+
+    if let Some(path) = &config.sarif_path {
+        write_sarif(&analysis.root.join(path), &analysis);
+    }
+
+    let output = match format {
+        cli::Format::Markdown => match report::render(&analysis.results, analysis.baselined) {
+            Some(report) => format!("{}\n\n{}", report.context, report.reason),
+            None => "No tools ran.".to_string(),
+        },
+        cli::Format::Json => {
+            let json = report::to_json(&analysis.results, analysis.baselined);
+            serde_json::to_string_pretty(&json).unwrap_or_default()
+        }
+        cli::Format::Sarif => {
+            let log = sarif::log(&analysis.results, &analysis.root);
+            serde_json::to_string_pretty(&log).unwrap_or_default()
+        }
+    };
+    println!("{}", output);
+    ExitCode::SUCCESS
+}
+
+/// `reviews baseline`: runs every enabled tool project-wide and records the
+/// parsed findings so later hook runs report only new ones.
+fn write_baseline(dir: &Path) -> ExitCode {
+    let config = config::Config::load(dir);
+    let project = project::ProjectInfo::detect(dir, config.root);
+    let root = project.root.clone();
+    let ctx = tools::ToolContext::new(project);
+    let results = run_tools_parallel(&config, &ctx, &[]);
+
+    let unparsed: Vec<_> = results
+        .iter()
+        .filter(|r| r.diagnostics.is_none() && !r.output.is_empty())
+        .map(|r| r.name.as_ref())
+        .collect();
+    if !unparsed.is_empty() {
+        eprintln!(
+            "reviews: warning: unparsed output from {} cannot be baselined",
+            unparsed.join(", ")
+        );
+    }
+
+    match baseline::write(&root, &results) {
+        Ok(count) => {
+            println!(
+                "reviews: wrote {} findings to {}",
+                count,
+                baseline::path(&root).display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("reviews: failed to write baseline: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Computes the configured diff scope, or `None` for project-wide runs.
+/// Falls back to project-wide results when git cannot produce a diff.
+fn diff_scope(config: &config::Config, project: &project::ProjectInfo) -> Option<diff::DiffScope> {
+    let staged = match config.scope {
+        config::Scope::Project => return None,
+        config::Scope::Diff => false,
+        config::Scope::Staged => true,
+    };
+    let scope = diff::DiffScope::compute(&project.root, staged, config.base.as_deref());
+    match &scope {
+        Some(s) if *DEBUG => eprintln!("reviews: debug: diff scope files={}", s.files().len()),
+        Some(_) => {}
+        None => eprintln!("reviews: warning: diff scope unavailable, reporting all findings"),
+    }
+    scope
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = match cli::parse(&args) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("reviews: {}\n{}", e, cli::USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let cwd = match std::env::current_dir() {
+        Ok(d) => d,
+        Err(e) => {
+            eprintln!("reviews: cannot determine cwd: {}", e);
+            return ExitCode::SUCCESS;
+        }
+    };
+
+    match command {
+        cli::Command::Hook { sarif } => {
+            run_hook(&cwd, sarif.as_deref());
+            ExitCode::SUCCESS
+        }
+        cli::Command::Run { format, path } => run_cli(&target_dir(&cwd, path), format),
+        cli::Command::Doctor { path } => {
+            let (report, healthy) = doctor::report(&target_dir(&cwd, path));
+            print!("{}", report);
+            if healthy {
+                ExitCode::SUCCESS
+            } else {
+                ExitCode::FAILURE
+            }
+        }
+        cli::Command::Baseline { path } => write_baseline(&target_dir(&cwd, path)),
+        cli::Command::Help => {
+            println!("{}", cli::USAGE);
+            ExitCode::SUCCESS
+        }
+    }
+}
+
+/// Resolves a subcommand's optional `PATH` argument against `cwd`.
+fn target_dir(cwd: &Path, path: Option<PathBuf>) -> PathBuf {
+    path.map_or_else(|| cwd.to_path_buf(), |p| cwd.join(p))
+}
+
+fn run_hook(cwd: &Path, sarif: Option<&Path>) {
     let mut input_str = String::new();
     let bytes_read = match io::stdin()
         .take((MAX_INPUT_SIZE + 1) as u64)
@@ -126,65 +482,94 @@ fn main() {
         return;
     }
 
-    let cwd = match std::env::current_dir() {
-        Ok(d) => d,
-        Err(e) => {
-            eprintln!("reviews: cannot determine cwd: {}", e);
-            return;
-        }
-    };
-
-    if let Some(json) = run(&input_str, &cwd) {
+    if let Some(json) = run(&input_str, cwd, sarif) {
         println!("{}", json);
     }
 }
 
+/// Runs the enabled file-capable tools selected by `post_tool_use`, each
+/// capped at its timeout.
+fn run_file_tools(config: &config::Config, ctx: &tools::ToolContext) -> Vec<tools::ToolResult> {
+    let post = &config.post_tool_use;
+    let handles = tools::TOOLS
+        .iter()
+        .filter(|t| t.files && post.runs(t.name))
+        .filter(|t| (t.settings)(&config.tools).enabled)
+        .map(|t| {
+            let limit = post.timeout;
+            let mut c = ctx.clone();
+            c.settings = (t.settings)(&config.tools).clone();
+            c.settings.timeout = Some(c.settings.timeout.map_or(limit, |d| d.min(limit)));
+            let run = t.run;
+            (t.name.into(), std::thread::spawn(move || run(&c)))
+        })
+        .collect();
+    join_tools(handles)
+}
+
+/// Without a diff scope, file-accepting tools lint only `paths` (relative to
+/// the project root) when any are given.
 fn run_tools_parallel(
     config: &config::Config,
-    project: &project::ProjectInfo,
+    ctx: &tools::ToolContext,
+    paths: &[PathBuf],
 ) -> Vec<tools::ToolResult> {
     use std::thread;
 
-    type ToolRunFn = fn(&project::ProjectInfo) -> tools::ToolResult;
-
-    struct Entry {
-        enabled: bool,
-        name: &'static str,
-        run: ToolRunFn,
+    let mut handles: Vec<ToolHandle> = Vec::new();
+    for t in tools::TOOLS {
+        let settings = (t.settings)(&config.tools);
+        if !config.runs(&[t.name, t.key], settings.enabled) {
+            continue;
+        }
+        let run = t.run;
+        if !t.per_package || ctx.project.packages.is_empty() {
+            let mut c = ctx.clone();
+            c.settings = settings.clone();
+            if t.files && c.files.is_none() && !paths.is_empty() {
+                c.files = Some(paths.to_vec());
+            }
+            handles.push((t.name.into(), thread::spawn(move || run(&c))));
+            continue;
+        }
+        // In a monorepo, whole-program tools run once per applicable package
+        // and report paths relative to the workspace root.
+        for package in ctx.project.packages.iter().filter(|p| (t.applies)(&p.info)) {
+            let c = tools::ToolContext {
+                project: package.info.clone(),
+                files: None,
+                settings: settings.clone(),
+            };
+            let label = format!("{} ({})", t.name, package.name);
+            let dir = package.dir.clone();
+            handles.push((
+                label.clone().into(),
+                thread::spawn(move || {
+                    let mut result = run(&c);
+                    result.name = label.into();
+                    result.rebase(&dir);
+                    result
+                }),
+            ));
+        }
     }
+    let runs = |t: &&config::CustomTool| config.runs(&[&t.name], t.enabled);
+    handles.extend(config.custom_tools.iter().filter(runs).map(|t| {
+        let c = ctx.clone();
+        let tool = t.clone();
+        (
+            t.name.clone().into(),
+            thread::spawn(move || tools::custom::run(&tool, &c)),
+        )
+    }));
 
-    let entries = vec![
-        Entry {
-            enabled: config.tools.knip,
-            name: "knip",
-            run: tools::knip::run,
-        },
-        Entry {
-            enabled: config.tools.oxlint,
-            name: "oxlint",
-            run: tools::oxlint::run,
-        },
-        Entry {
-            enabled: config.tools.tsgo,
-            name: "tsgo",
-            run: tools::tsgo::run,
-        },
-        Entry {
-            enabled: config.tools.react_doctor,
-            name: "react-doctor",
-            run: tools::react_doctor::run,
-        },
-    ];
+    join_tools(handles)
+}
 
-    let handles: Vec<_> = entries
-        .into_iter()
-        .filter(|e| e.enabled)
-        .map(|e| {
-            let p = project.clone();
-            (e.name, thread::spawn(move || (e.run)(&p)))
-        })
-        .collect();
+type ToolHandle = (Cow<'static, str>, JoinHandle<tools::ToolResult>);
 
+/// Waits for every spawned tool; a panicked thread counts as skipped.
+fn join_tools(handles: Vec<ToolHandle>) -> Vec<tools::ToolResult> {
     handles
         .into_iter()
         .map(|(name, handle)| match handle.join() {
@@ -201,6 +586,10 @@ fn run_tools_parallel(
 mod tests {
     use super::*;
 
+    fn parse_skill_name(input: &str) -> Option<String> {
+        parse_input(input)?.tool_input.skill
+    }
+
     #[test]
     fn parse_skill_name_valid() {
         let input = r#"{"tool_name": "Skill", "tool_input": {"skill": "audit"}}"#;
@@ -229,27 +618,31 @@ mod tests {
     fn build_output_partial_success() {
         let results = vec![
             tools::ToolResult {
-                name: "knip",
+                name: "knip".into(),
                 output: "result1".into(),
                 success: true,
+                diagnostics: None,
             },
             tools::ToolResult {
-                name: "oxlint",
+                name: "oxlint".into(),
                 output: "result2".into(),
                 success: true,
+                diagnostics: None,
             },
             tools::ToolResult {
-                name: "tsgo",
+                name: "tsgo".into(),
                 output: "result3".into(),
                 success: true,
+                diagnostics: None,
             },
             tools::ToolResult {
-                name: "react-doctor",
+                name: "react-doctor".into(),
                 output: String::new(),
                 success: false,
+                diagnostics: None,
             },
         ];
-        let json = build_output(&results).unwrap();
+        let json = build_output(&results, 0, true).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed["decision"], "approve");
         assert!(parsed["reason"].as_str().unwrap().contains("3/4"));
@@ -263,42 +656,66 @@ mod tests {
     fn build_output_all_empty_output() {
         let results = vec![
             tools::ToolResult {
-                name: "knip",
+                name: "knip".into(),
                 output: String::new(),
                 success: false,
+                diagnostics: None,
             },
             tools::ToolResult {
-                name: "oxlint",
+                name: "oxlint".into(),
                 output: String::new(),
                 success: false,
+                diagnostics: None,
             },
         ];
-        let json = build_output(&results).unwrap();
+        let json = build_output(&results, 0, true).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert_eq!(parsed["decision"], "approve");
         assert!(parsed["reason"].as_str().unwrap().contains("0/2"));
     }
 
+    #[test]
+    fn build_output_uses_hook_specific_output() {
+        let results = vec![tools::ToolResult {
+            name: "oxlint".into(),
+            output: "issues".into(),
+            success: false,
+            diagnostics: None,
+        }];
+        let json = build_output(&results, 0, false).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(parsed.get("decision").is_none());
+        let output = &parsed["hookSpecificOutput"];
+        assert_eq!(output["hookEventName"], "PreToolUse");
+        assert_eq!(output["permissionDecision"], "allow");
+        let reason = output["permissionDecisionReason"].as_str().unwrap();
+        assert!(reason.contains("1/1"));
+        let ctx = output["additionalContext"].as_str().unwrap();
+        assert!(ctx.contains("issues"));
+    }
+
     #[test]
     fn build_output_empty_slice() {
-        assert!(build_output(&[]).is_none());
+        assert!(build_output(&[], 0, true).is_none());
     }
 
     #[test]
     fn build_output_includes_failed_with_output() {
         let results = vec![
             tools::ToolResult {
-                name: "oxlint",
+                name: "oxlint".into(),
                 output: "warning: unused variable".into(),
                 success: false,
+                diagnostics: None,
             },
             tools::ToolResult {
-                name: "knip",
+                name: "knip".into(),
                 output: String::new(),
                 success: false,
+                diagnostics: None,
             },
         ];
-        let json = build_output(&results).unwrap();
+        let json = build_output(&results, 0, true).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         let reason = parsed["reason"].as_str().unwrap();
         assert!(reason.contains("1/2"));
@@ -312,29 +729,79 @@ mod tests {
     fn build_output_excludes_successful_but_empty() {
         let results = vec![
             tools::ToolResult {
-                name: "knip",
+                name: "knip".into(),
                 output: String::new(),
                 success: true,
+                diagnostics: None,
             },
             tools::ToolResult {
-                name: "oxlint",
+                name: "oxlint".into(),
                 output: "issues".into(),
                 success: true,
+                diagnostics: None,
             },
         ];
-        let json = build_output(&results).unwrap();
+        let json = build_output(&results, 0, true).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
         assert!(parsed["reason"].as_str().unwrap().contains("1/2"));
         let ctx = parsed["additionalContext"].as_str().unwrap();
         assert!(!ctx.contains("knip"));
     }
 
+    #[test]
+    fn build_output_renders_parsed_diagnostics() {
+        let results = vec![
+            tools::ToolResult {
+                name: "tsgo".into(),
+                output: "src/a.ts(1,5): error TS2322: bad".into(),
+                success: false,
+                diagnostics: Some(vec![diagnostic::Diagnostic {
+                    tool: "tsgo".into(),
+                    rule: Some("TS2322".into()),
+                    severity: diagnostic::Severity::Error,
+                    file: Some("src/a.ts".into()),
+                    line: Some(1),
+                    column: Some(5),
+                    message: "bad".into(),
+                    ..Default::default()
+                }]),
+            },
+            tools::ToolResult {
+                name: "oxlint".into(),
+                output: r#"{"diagnostics": []}"#.into(),
+                success: true,
+                diagnostics: Some(vec![]),
+            },
+        ];
+        let json = build_output(&results, 0, true).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(parsed["reason"].as_str().unwrap().contains("1/2"));
+        let ctx = parsed["additionalContext"].as_str().unwrap();
+        assert!(ctx.contains("## tsgo (1 error)"));
+        assert!(ctx.contains("- src/a.ts:1:5: error[TS2322] bad"));
+        assert!(!ctx.contains("oxlint"));
+    }
+
+    #[test]
+    fn build_output_reports_baselined_count() {
+        let results = vec![tools::ToolResult {
+            name: "oxlint".into(),
+            output: "issues".into(),
+            success: false,
+            diagnostics: None,
+        }];
+        let json = build_output(&results, 143, true).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let reason = parsed["reason"].as_str().unwrap();
+        assert!(reason.ends_with("143 baselined findings hidden"));
+    }
+
     #[test]
     fn run_returns_none_for_non_matching_skill() {
         let tmp = test_utils::TempDir::new("run-nonmatch");
         std::fs::create_dir_all(tmp.join(".git")).unwrap();
         let input = r#"{"tool_name": "Skill", "tool_input": {"skill": "commit"}}"#;
-        assert!(run(input, &tmp).is_none());
+        assert!(run(input, &tmp, None).is_none());
     }
 
     #[test]
@@ -347,14 +814,14 @@ mod tests {
         )
         .unwrap();
         let input = r#"{"tool_name": "Skill", "tool_input": {"skill": "review"}}"#;
-        assert!(run(input, &tmp).is_none());
+        assert!(run(input, &tmp, None).is_none());
     }
 
     #[test]
     fn run_returns_none_for_invalid_input() {
         let tmp = test_utils::TempDir::new("run-invalid");
         std::fs::create_dir_all(tmp.join(".git")).unwrap();
-        assert!(run("not json", &tmp).is_none());
+        assert!(run("not json", &tmp, None).is_none());
     }
 
     #[test]
@@ -367,7 +834,7 @@ mod tests {
         )
         .unwrap();
         let input = r#"{"tool_name": "Skill", "tool_input": {"skill": "review"}}"#;
-        assert!(run(input, &tmp).is_none());
+        assert!(run(input, &tmp, None).is_none());
     }
 
     #[test]
@@ -375,7 +842,325 @@ mod tests {
         let tmp = test_utils::TempDir::new("run-match");
         std::fs::create_dir_all(tmp.join(".git")).unwrap();
         let input = r#"{"tool_name": "Skill", "tool_input": {"skill": "review"}}"#;
-        let _ = run(input, &tmp);
+        let _ = run(input, &tmp, None);
+    }
+
+    #[test]
+    fn run_writes_sarif_without_changing_stdout() {
+        let tmp = test_utils::TempDir::new("run-sarif");
+        std::fs::create_dir_all(tmp.join(".git")).unwrap();
+        std::fs::write(
+            tmp.join(".claude-reviews.json"),
+            // An empty profile tool list runs nothing.
+            r#"{"sarif_path": "reviews.sarif", "profiles": {"review": {"tools": []}}}"#,
+        )
+        .unwrap();
+        let input = r#"{"tool_name": "Skill", "tool_input": {"skill": "review"}}"#;
+        assert!(run(input, &tmp, None).is_none());
+        let log = std::fs::read_to_string(tmp.join("reviews.sarif")).unwrap();
+        assert!(log.contains("\"version\": \"2.1.0\""));
+
+        run(input, &tmp, Some(Path::new("cli.sarif")));
+        assert!(tmp.join("cli.sarif").exists());
+    }
+
+    #[test]
+    fn per_package_tools_run_in_each_workspace_package() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = test_utils::TempDir::new("run-workspace");
+        std::fs::create_dir_all(tmp.join(".git")).unwrap();
+        std::fs::write(tmp.join("package.json"), r#"{"workspaces": ["apps/*"]}"#).unwrap();
+        for app in ["web", "docs", "cli"] {
+            let dir = tmp.join("apps").join(app);
+            std::fs::create_dir_all(&dir).unwrap();
+            std::fs::write(dir.join("package.json"), format!(r#"{{"name": "{app}"}}"#)).unwrap();
+            if app != "cli" {
+                std::fs::write(dir.join("tsconfig.json"), "{}").unwrap();
+            }
+        }
+        let bin_dir = tmp.join("node_modules/.bin");
+        std::fs::create_dir_all(&bin_dir).unwrap();
+        std::fs::write(
+            bin_dir.join("tsgo"),
+            "#!/bin/sh\necho \"src/a.ts(1,2): error TS2322: bad.\"\nexit 2\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(bin_dir.join("tsgo"), std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+
+        let config = config::Config {
+            only_tools: Some(vec!["tsgo".to_string()]),
+            ..Default::default()
+        };
+        let ctx = tools::ToolContext::new(project::ProjectInfo::detect(&tmp, config.root));
+        let results = run_tools_parallel(&config, &ctx, &[]);
+
+        let names: Vec<_> = results.iter().map(|r| r.name.as_ref()).collect();
+        assert_eq!(names, vec!["tsgo (docs)", "tsgo (web)"]);
+        let web = results[1].diagnostics.as_ref().unwrap();
+        assert_eq!(web[0].tool, "tsgo");
+        assert_eq!(web[0].file.as_deref(), Some(Path::new("apps/web/src/a.ts")));
+    }
+
+    #[test]
+    fn requested_paths_are_passed_to_file_tools() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = test_utils::TempDir::new("run-path-args");
+        std::fs::create_dir_all(tmp.join(".git")).unwrap();
+        std::fs::create_dir_all(tmp.join("lib")).unwrap();
+        let bin_dir = tmp.join("node_modules/.bin");
+        std::fs::create_dir_all(&bin_dir).unwrap();
+        let oxlint = bin_dir.join("oxlint");
+        std::fs::write(&oxlint, "#!/bin/sh\necho \"$@\"\n").unwrap();
+        std::fs::set_permissions(&oxlint, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config = config::Config {
+            only_tools: Some(vec!["oxlint".to_string()]),
+            ..Default::default()
+        };
+        let ctx = tools::ToolContext::new(project::ProjectInfo::detect(&tmp, config.root));
+        let results = run_tools_parallel(&config, &ctx, &[PathBuf::from("lib")]);
+        assert!(results[0].output.ends_with("lib"), "{}", results[0].output);
+    }
+
+    /// A project whose `oxlint` reports one error in `src/a.ts`.
+    fn project_with_oxlint_error(name: &str) -> test_utils::TempDir {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = test_utils::TempDir::new(name);
+        std::fs::create_dir_all(tmp.join(".git")).unwrap();
+        std::fs::create_dir_all(tmp.join("src")).unwrap();
+        std::fs::write(tmp.join("src/a.ts"), "debugger;\n").unwrap();
+        let bin_dir = tmp.join("node_modules/.bin");
+        std::fs::create_dir_all(&bin_dir).unwrap();
+        std::fs::write(
+            bin_dir.join("oxlint"),
+            r#"#!/bin/sh
+echo '{"diagnostics": [{"message": "Unexpected debugger statement", "code": "eslint(no-debugger)", "severity": "error", "filename": "src/a.ts", "labels": [{"span": {"line": 1, "column": 1}}]}]}'
+exit 1
+"#,
+        )
+        .unwrap();
+        std::fs::set_permissions(
+            bin_dir.join("oxlint"),
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        tmp
+    }
+
+    fn edit_input(tool: &str, file: &Path) -> String {
+        serde_json::json!({
+            "hook_event_name": "PostToolUse",
+            "tool_name": tool,
+            "tool_input": {"file_path": file}
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn parse_input_reads_edited_file() {
+        let hook = parse_input(&edit_input("Write", Path::new("/repo/src/a.ts"))).unwrap();
+        assert_eq!(hook.tool_name, "Write");
+        assert_eq!(
+            hook.tool_input.file_path.as_deref(),
+            Some(Path::new("/repo/src/a.ts"))
+        );
+        assert!(hook.tool_input.skill.is_none());
+    }
+
+    #[test]
+    fn post_edit_blocks_with_findings_for_edited_file() {
+        let tmp = project_with_oxlint_error("run-post-edit");
+        for tool in EDIT_TOOLS {
+            let json = run(&edit_input(tool, &tmp.join("src/a.ts")), &tmp, None).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(parsed["decision"], "block");
+            assert!(parsed["reason"].as_str().unwrap().contains("1 with issues"));
+            let output = &parsed["hookSpecificOutput"];
+            assert_eq!(output["hookEventName"], "PostToolUse");
+            let ctx = output["additionalContext"].as_str().unwrap();
+            assert!(ctx.contains("## oxlint (1 error)"));
+            assert!(ctx.contains("src/a.ts:1:1: error[eslint(no-debugger)]"));
+        }
+
+        std::fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"legacy_output": true}"#,
+        )
+        .unwrap();
+        let json = run(&edit_input("Edit", &tmp.join("src/a.ts")), &tmp, None).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert!(parsed.get("hookSpecificOutput").is_none());
+        let reason = parsed["reason"].as_str().unwrap();
+        assert!(reason.contains("## oxlint (1 error)"));
+    }
+
+    #[test]
+    fn post_edit_ignores_pre_tool_use_and_other_events() {
+        let tmp = project_with_oxlint_error("run-post-edit-events");
+        let file = tmp.join("src/a.ts");
+        for event in ["PreToolUse", "SubagentStop"] {
+            let input = serde_json::json!({
+                "hook_event_name": event,
+                "tool_name": "Edit",
+                "tool_input": {"file_path": file}
+            });
+            assert!(run(&input.to_string(), &tmp, None).is_none());
+        }
+    }
+
+    #[test]
+    fn session_start_adds_findings_as_context() {
+        let tmp = project_with_oxlint_error("run-session-start");
+        let input = r#"{"hook_event_name": "SessionStart", "source": "startup"}"#;
+        let json = run(input, &tmp, None).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let output = &parsed["hookSpecificOutput"];
+        assert_eq!(output["hookEventName"], "SessionStart");
+        let ctx = output["additionalContext"].as_str().unwrap();
+        assert!(ctx.contains("## oxlint (1 error)"), "{ctx}");
+    }
+
+    #[test]
+    fn post_edit_is_silent_when_clean_or_irrelevant() {
+        let tmp = project_with_oxlint_error("run-post-edit-clean");
+        std::fs::write(tmp.join("README.md"), "# readme\n").unwrap();
+        // No file-capable tool lints Markdown.
+        assert!(run(&edit_input("Edit", &tmp.join("README.md")), &tmp, None).is_none());
+        // Files outside the project are ignored.
+        let outside = edit_input("Edit", Path::new("/tmp/elsewhere.ts"));
+        assert!(run(&outside, &tmp, None).is_none());
+        // Other tools never trigger a lint.
+        assert!(run(&edit_input("Read", &tmp.join("src/a.ts")), &tmp, None).is_none());
+    }
+
+    #[test]
+    fn post_edit_respects_config_section() {
+        let tmp = project_with_oxlint_error("run-post-edit-config");
+        let input = edit_input("Edit", &tmp.join("src/a.ts"));
+        std::fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"post_tool_use": {"tools": ["eslint"]}}"#,
+        )
+        .unwrap();
+        assert!(run(&input, &tmp, None).is_none());
+
+        std::fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"post_tool_use": {"enabled": false}}"#,
+        )
+        .unwrap();
+        assert!(run(&input, &tmp, None).is_none());
+    }
+
+    #[test]
+    fn skill_args_select_tools_and_paths() {
+        let tmp = project_with_oxlint_error("run-skill-args");
+        std::fs::create_dir_all(tmp.join("lib")).unwrap();
+        let review = |args: &str| {
+            let input = serde_json::json!({
+                "tool_name": "Skill",
+                "tool_input": {"skill": "review", "args": args}
+            });
+            let json = run(&input.to_string(), &tmp, None).unwrap();
+            let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+            parsed["hookSpecificOutput"].clone()
+        };
+
+        let output = review("--tools oxlint");
+        let reason = output["permissionDecisionReason"].as_str().unwrap();
+        assert!(reason.contains("1/1"));
+        let ctx = output["additionalContext"].as_str().unwrap();
+        assert!(ctx.contains("## oxlint (1 error)"));
+
+        let output = review("lib --tools oxlint");
+        let reason = output["permissionDecisionReason"].as_str().unwrap();
+        assert!(reason.contains("0/1"));
+    }
+
+    #[test]
+    fn profile_selects_tools_for_its_skill() {
+        let tmp = project_with_oxlint_error("run-profile");
+        std::fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"profiles": {"lint": {"tools": ["oxlint"]}}}"#,
+        )
+        .unwrap();
+        let input = r#"{"tool_name": "Skill", "tool_input": {"skill": "lint"}}"#;
+        let json = run(input, &tmp, None).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let output = &parsed["hookSpecificOutput"];
+        let reason = output["permissionDecisionReason"].as_str().unwrap();
+        assert!(reason.contains("1/1"));
+
+        let mut config = config::Config::load(&tmp);
+        config.apply_profile("lint");
+        let analysis = analyze(&config, &tmp);
+        let names: Vec<_> = analysis.results.iter().map(|r| r.name.as_ref()).collect();
+        assert_eq!(names, vec!["oxlint"]);
+
+        // Skill arguments override the profile. knip runs but skips itself
+        // without a `package.json`.
+        skill_args::SkillArgs::parse("--tools knip,oxlint").apply(&mut config, &tmp);
+        let analysis = analyze(&config, &tmp);
+        let names: Vec<_> = analysis.results.iter().map(|r| r.name.as_ref()).collect();
+        assert_eq!(names, vec!["knip", "oxlint"]);
+        let knip = &analysis.results[0];
+        assert!(knip.output.is_empty() && knip.diagnostics.is_none());
+        assert!(analysis.results[1].diagnostics.is_some());
+    }
+
+    #[test]
+    fn stop_gate_blocks_on_new_errors_until_retries_run_out() {
+        let tmp = project_with_oxlint_error("run-stop");
+        std::fs::remove_dir(tmp.join(".git")).unwrap();
+        std::fs::write(tmp.join("README.md"), "# readme\n").unwrap();
+        for args in [
+            &["init", "-q"][..],
+            &["add", "README.md"],
+            &["commit", "-qm", "init"],
+        ] {
+            let ok = std::process::Command::new("git")
+                .args(["-c", "user.name=t", "-c", "user.email=t@example.com"])
+                .args(args)
+                .current_dir(&*tmp)
+                .status()
+                .unwrap()
+                .success();
+            assert!(ok, "git {args:?} failed");
+        }
+        let session = format!("test-{}", std::process::id());
+        let input =
+            serde_json::json!({"hook_event_name": "Stop", "session_id": session}).to_string();
+
+        // Opt-in: the default config lets the agent stop.
+        assert!(run(&input, &tmp, None).is_none());
+
+        std::fs::write(
+            tmp.join(".claude-reviews.json"),
+            r#"{"stop_gate": {"enabled": true, "max_retries": 1}}"#,
+        )
+        .unwrap();
+        let json = run(&input, &tmp, None).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed["decision"], "block");
+        let reason = parsed["reason"].as_str().unwrap();
+        assert!(reason.contains("(attempt 1/1)"));
+        assert!(reason.contains("- src/a.ts:1:1: error[eslint(no-debugger)]"));
+
+        assert!(run(&input, &tmp, None).is_none());
+        assert!(run(&input, &tmp, None).is_some());
+        let session_file = format!(".git/claude-reviews-stop-{session}");
+        assert!(tmp.join(session_file).is_file());
+
+        let input = serde_json::json!({"hook_event_name": "Stop"}).to_string();
+        for _ in 0..3 {
+            let json = run(&input, &tmp, None).unwrap();
+            assert!(!json.contains("(attempt"), "{json}");
+        }
     }
 
     #[test]
@@ -0,0 +1,173 @@
+use serde_json::{Value, json};
+
+/// The hook event being answered, from `hook_event_name`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    PreToolUse,
+    PostToolUse,
+    Stop,
+    SessionStart,
+}
+
+impl Event {
+    /// Older Claude Code versions omit `hook_event_name`; their only events
+    /// this binary handled were `PreToolUse` on skills, so edits imply
+    /// `PostToolUse`. Other events are ignored.
+    pub fn parse(name: &str, is_edit: bool) -> Option<Self> {
+        match name {
+            "PreToolUse" => Some(Self::PreToolUse),
+            "PostToolUse" => Some(Self::PostToolUse),
+            "Stop" => Some(Self::Stop),
+            "SessionStart" => Some(Self::SessionStart),
+            "" if is_edit => Some(Self::PostToolUse),
+            "" => Some(Self::PreToolUse),
+            _ => None,
+        }
+    }
+}
+
+/// Allows the skill call and hands the agent the analysis as context.
+/// `legacy` selects the top-level `decision`/`reason`/`additionalContext`
+/// fields older Claude Code versions read.
+pub fn pre_tool_use(reason: &str, context: &str, legacy: bool) -> Value {
+    if legacy {
+        return json!({
+            "decision": "approve",
+            "reason": reason,
+            "additionalContext": context
+        });
+    }
+    json!({
+        "hookSpecificOutput": {
+            "hookEventName": "PreToolUse",
+            "permissionDecision": "allow",
+            "permissionDecisionReason": reason,
+            "additionalContext": context
+        }
+    })
+}
+
+/// Feeds findings for an edited file back to the agent. The legacy shape has
+/// no separate context field, so it is appended to `reason`.
+pub fn post_tool_use(reason: &str, context: &str, legacy: bool) -> Value {
+    if legacy {
+        return json!({
+            "decision": "block",
+            "reason": format!("{reason}\n\n{context}")
+        });
+    }
+    json!({
+        "decision": "block",
+        "reason": reason,
+        "hookSpecificOutput": {
+            "hookEventName": "PostToolUse",
+            "additionalContext": context
+        }
+    })
+}
+
+/// Keeps the agent working. `Stop` has no event-specific fields, so both
+/// formats are the same.
+pub fn stop(reason: &str) -> Value {
+    json!({
+        "decision": "block",
+        "reason": reason
+    })
+}
+
+/// Hands the agent the analysis when a session starts. Claude Code has only
+/// ever read `SessionStart` context from `hookSpecificOutput`, so there is
+/// no legacy shape.
+pub fn session_start(context: &str) -> Value {
+    json!({
+        "hookSpecificOutput": {
+            "hookEventName": "SessionStart",
+            "additionalContext": context
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn event_from_name_or_tool() {
+        assert_eq!(Event::parse("PreToolUse", false), Some(Event::PreToolUse));
+        assert_eq!(Event::parse("PostToolUse", true), Some(Event::PostToolUse));
+        assert_eq!(Event::parse("Stop", false), Some(Event::Stop));
+        assert_eq!(Event::parse("", false), Some(Event::PreToolUse));
+        assert_eq!(Event::parse("", true), Some(Event::PostToolUse));
+        assert_eq!(
+            Event::parse("SessionStart", false),
+            Some(Event::SessionStart)
+        );
+        assert_eq!(Event::parse("SubagentStop", false), None);
+    }
+
+    #[test]
+    fn pre_tool_use_shapes() {
+        assert_eq!(
+            pre_tool_use("Pre-flight: 1/1 tools reported", "# ctx", false),
+            json!({
+                "hookSpecificOutput": {
+                    "hookEventName": "PreToolUse",
+                    "permissionDecision": "allow",
+                    "permissionDecisionReason": "Pre-flight: 1/1 tools reported",
+                    "additionalContext": "# ctx"
+                }
+            })
+        );
+        assert_eq!(
+            pre_tool_use("Pre-flight: 1/1 tools reported", "# ctx", true),
+            json!({
+                "decision": "approve",
+                "reason": "Pre-flight: 1/1 tools reported",
+                "additionalContext": "# ctx"
+            })
+        );
+    }
+
+    #[test]
+    fn post_tool_use_shapes() {
+        assert_eq!(
+            post_tool_use("Pre-flight: 1/1 tools reported", "# ctx", false),
+            json!({
+                "decision": "block",
+                "reason": "Pre-flight: 1/1 tools reported",
+                "hookSpecificOutput": {
+                    "hookEventName": "PostToolUse",
+                    "additionalContext": "# ctx"
+                }
+            })
+        );
+        assert_eq!(
+            post_tool_use("Pre-flight: 1/1 tools reported", "# ctx", true),
+            json!({
+                "decision": "block",
+                "reason": "Pre-flight: 1/1 tools reported\n\n# ctx"
+            })
+        );
+    }
+
+    #[test]
+    fn session_start_shape() {
+        assert_eq!(
+            session_start("# ctx"),
+            json!({
+                "hookSpecificOutput": {
+                    "hookEventName": "SessionStart",
+                    "additionalContext": "# ctx"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn stop_shape() {
+        assert_eq!(
+            stop("Quality gate: 1 new error remains"),
+            json!({"decision": "block", "reason": "Quality gate: 1 new error remains"})
+        );
+    }
+}
@@ -1,27 +1,126 @@
+use crate::config::RootStrategy;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
-#[derive(Debug, Clone)]
+/// Flat configs first, then the legacy `.eslintrc` family.
+const ESLINT_CONFIGS: &[&str] = &[
+    "eslint.config.js",
+    "eslint.config.mjs",
+    "eslint.config.cjs",
+    "eslint.config.ts",
+    "eslint.config.mts",
+    "eslint.config.cts",
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.json",
+    ".eslintrc.yml",
+    ".eslintrc.yaml",
+    ".eslintrc",
+];
+
+#[derive(Debug, Clone, Default)]
 pub struct ProjectInfo {
     pub root: PathBuf,
     pub has_package_json: bool,
     pub has_tsconfig: bool,
     pub has_react: bool,
+    pub has_vue: bool,
+    pub has_svelte: bool,
+    pub has_astro: bool,
+    pub has_next: bool,
+    /// An ESLint config file, or `eslintConfig` in `package.json`.
+    pub has_eslint_config: bool,
+    /// `biome.json` or `biome.jsonc`.
+    pub has_biome_config: bool,
+    pub has_cargo_toml: bool,
+    /// `pyproject.toml`, `setup.cfg` or `requirements.txt` at the root.
+    pub has_python: bool,
+    pub has_go_mod: bool,
+    /// Names from `dependencies`, `devDependencies` and `peerDependencies`.
+    pub dependencies: BTreeSet<String>,
+    /// Workspace packages when the root is a monorepo; empty otherwise.
+    pub packages: Vec<Package>,
+}
+
+/// One workspace package, detected like a standalone project.
+#[derive(Debug, Clone)]
+pub struct Package {
+    /// `name` from its `package.json`, or `dir` when unnamed.
+    pub name: String,
+    /// Directory relative to the workspace root.
+    pub dir: PathBuf,
+    /// Flags for the package itself; `info.root` is the package directory.
+    pub info: ProjectInfo,
 }
 
 impl ProjectInfo {
-    pub fn detect(dir: &Path) -> Self {
-        let root = Self::find_root(dir);
+    pub fn detect(dir: &Path, strategy: RootStrategy) -> Self {
+        let root = match strategy {
+            RootStrategy::Git => Self::find_root(dir),
+            RootStrategy::NearestPackage => Self::find_package_root(dir),
+        };
+        let (mut info, pkg_json) = Self::inspect(root);
+        let patterns = crate::workspace::patterns(&info.root, pkg_json.as_ref());
+        info.packages = crate::workspace::expand(&info.root, &patterns)
+            .into_iter()
+            .map(|dir| {
+                let (info, pkg_json) = Self::inspect(info.root.join(&dir));
+                let name = pkg_json
+                    .as_ref()
+                    .and_then(|p| p.get("name"))
+                    .and_then(|n| n.as_str())
+                    .map_or_else(|| dir.to_string_lossy().into_owned(), str::to_string);
+                Package { name, dir, info }
+            })
+            .collect();
+        info
+    }
+
+    /// Detects the flags of the directory `root` alone, returning its parsed
+    /// `package.json` for further inspection.
+    fn inspect(root: PathBuf) -> (Self, Option<serde_json::Value>) {
         let has_tsconfig = root.join("tsconfig.json").exists();
         let pkg_json = Self::read_package_json(&root);
         let has_package_json = pkg_json.is_some();
-        let has_react = pkg_json.as_ref().is_some_and(Self::has_react_dep);
+        let dependencies = pkg_json
+            .as_ref()
+            .map(Self::dependency_names)
+            .unwrap_or_default();
+        let has_react = dependencies.contains("react");
+        let has_vue = dependencies.contains("vue");
+        let has_svelte = dependencies.contains("svelte");
+        let has_astro = dependencies.contains("astro");
+        let has_next = dependencies.contains("next");
+        let has_eslint_config = ESLINT_CONFIGS.iter().any(|f| root.join(f).is_file())
+            || pkg_json
+                .as_ref()
+                .is_some_and(|p| p.get("eslintConfig").is_some());
+        let has_biome_config =
+            root.join("biome.json").is_file() || root.join("biome.jsonc").is_file();
+        let has_cargo_toml = root.join("Cargo.toml").exists();
+        let has_python = ["pyproject.toml", "setup.cfg", "requirements.txt"]
+            .iter()
+            .any(|f| root.join(f).exists());
+        let has_go_mod = root.join("go.mod").exists();
 
-        Self {
+        let info = Self {
             root,
             has_package_json,
             has_tsconfig,
             has_react,
-        }
+            has_vue,
+            has_svelte,
+            has_astro,
+            has_next,
+            has_eslint_config,
+            has_biome_config,
+            has_cargo_toml,
+            has_python,
+            has_go_mod,
+            dependencies,
+            packages: Vec::new(),
+        };
+        (info, pkg_json)
     }
 
     fn find_root(start: &Path) -> PathBuf {
@@ -31,6 +130,17 @@ impl ProjectInfo {
         .unwrap_or_else(|| start.to_path_buf())
     }
 
+    /// The closest ancestor of `start` with a `package.json`, not crossing the
+    /// git boundary; the git root when there is none.
+    fn find_package_root(start: &Path) -> PathBuf {
+        crate::traverse::walk_ancestors(start, |dir| {
+            dir.join("package.json")
+                .is_file()
+                .then(|| dir.to_path_buf())
+        })
+        .unwrap_or_else(|| Self::find_root(start))
+    }
+
     fn read_package_json(root: &Path) -> Option<serde_json::Value> {
         let pkg_path = root.join("package.json");
         let content = match std::fs::read_to_string(&pkg_path) {
@@ -46,15 +156,12 @@ impl ProjectInfo {
         }
     }
 
-    fn has_react_dep(json: &serde_json::Value) -> bool {
-        for key in ["dependencies", "devDependencies", "peerDependencies"] {
-            if let Some(deps) = json.get(key).and_then(|v| v.as_object())
-                && deps.contains_key("react")
-            {
-                return true;
-            }
-        }
-        false
+    fn dependency_names(json: &serde_json::Value) -> BTreeSet<String> {
+        ["dependencies", "devDependencies", "peerDependencies"]
+            .iter()
+            .filter_map(|key| json.get(key).and_then(|v| v.as_object()))
+            .flat_map(|deps| deps.keys().cloned())
+            .collect()
     }
 }
 
@@ -70,7 +177,7 @@ mod tests {
         fs::create_dir_all(tmp.join(".git")).unwrap();
         fs::write(tmp.join("package.json"), "{}").unwrap();
 
-        let info = ProjectInfo::detect(&tmp);
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
         assert!(info.has_package_json);
     }
 
@@ -79,7 +186,7 @@ mod tests {
         let tmp = TempDir::new("project-nopkg");
         fs::create_dir_all(tmp.join(".git")).unwrap();
 
-        let info = ProjectInfo::detect(&tmp);
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
         assert!(!info.has_package_json);
     }
 
@@ -93,7 +200,7 @@ mod tests {
         )
         .unwrap();
 
-        let info = ProjectInfo::detect(&tmp);
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
         assert!(info.has_react);
     }
 
@@ -107,10 +214,41 @@ mod tests {
         )
         .unwrap();
 
-        let info = ProjectInfo::detect(&tmp);
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
         assert!(info.has_react);
     }
 
+    #[test]
+    fn detects_frameworks() {
+        let tmp = TempDir::new("project-frameworks");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join("package.json"),
+            r#"{"dependencies": {"vue": "^3", "next": "^15"}, "devDependencies": {"astro": "^5"}}"#,
+        )
+        .unwrap();
+
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
+        assert!(info.has_vue && info.has_astro && info.has_next);
+        assert!(!info.has_svelte);
+    }
+
+    #[test]
+    fn collects_dependency_names() {
+        let tmp = TempDir::new("project-deps");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join("package.json"),
+            r#"{"dependencies": {"vue": "^3"}, "devDependencies": {"eslint": "^9"}}"#,
+        )
+        .unwrap();
+
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
+        assert!(info.dependencies.contains("vue"));
+        assert!(info.dependencies.contains("eslint"));
+        assert!(!info.dependencies.contains("react"));
+    }
+
     #[test]
     fn no_react_dependency() {
         let tmp = TempDir::new("project-noreact");
@@ -121,7 +259,7 @@ mod tests {
         )
         .unwrap();
 
-        let info = ProjectInfo::detect(&tmp);
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
         assert!(!info.has_react);
     }
 
@@ -135,7 +273,7 @@ mod tests {
         )
         .unwrap();
 
-        let info = ProjectInfo::detect(&tmp);
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
         assert!(info.has_react);
     }
 
@@ -145,7 +283,131 @@ mod tests {
         fs::create_dir_all(tmp.join(".git")).unwrap();
         fs::write(tmp.join("package.json"), "not valid json").unwrap();
 
-        let info = ProjectInfo::detect(&tmp);
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
         assert!(!info.has_react);
     }
+
+    #[test]
+    fn detects_cargo_toml() {
+        let tmp = TempDir::new("project-cargo");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("Cargo.toml"), "[package]\nname = \"demo\"\n").unwrap();
+
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
+        assert!(info.has_cargo_toml);
+        assert!(!info.has_package_json);
+    }
+
+    #[test]
+    fn detects_python_project() {
+        let tmp = TempDir::new("project-python");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        assert!(!ProjectInfo::detect(&tmp, RootStrategy::Git).has_python);
+
+        fs::write(tmp.join("requirements.txt"), "requests\n").unwrap();
+        assert!(ProjectInfo::detect(&tmp, RootStrategy::Git).has_python);
+    }
+
+    #[test]
+    fn detects_go_module() {
+        let tmp = TempDir::new("project-go");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("go.mod"), "module example.com/demo\n").unwrap();
+
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
+        assert!(info.has_go_mod);
+        assert!(!info.has_python);
+    }
+
+    #[test]
+    fn detects_eslint_config() {
+        let tmp = TempDir::new("project-eslint");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("package.json"), "{}").unwrap();
+        assert!(!ProjectInfo::detect(&tmp, RootStrategy::Git).has_eslint_config);
+
+        fs::write(tmp.join(".eslintrc.json"), "{}").unwrap();
+        assert!(ProjectInfo::detect(&tmp, RootStrategy::Git).has_eslint_config);
+
+        fs::remove_file(tmp.join(".eslintrc.json")).unwrap();
+        fs::write(tmp.join("package.json"), r#"{"eslintConfig": {}}"#).unwrap();
+        assert!(ProjectInfo::detect(&tmp, RootStrategy::Git).has_eslint_config);
+    }
+
+    #[test]
+    fn detects_workspace_packages() {
+        let tmp = TempDir::new("project-workspace");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(
+            tmp.join("package.json"),
+            r#"{"private": true, "workspaces": ["apps/*", "packages/*"]}"#,
+        )
+        .unwrap();
+        fs::create_dir_all(tmp.join("apps/web")).unwrap();
+        fs::write(
+            tmp.join("apps/web/package.json"),
+            r#"{"name": "@acme/web", "dependencies": {"react": "^19"}}"#,
+        )
+        .unwrap();
+        fs::write(tmp.join("apps/web/tsconfig.json"), "{}").unwrap();
+        fs::create_dir_all(tmp.join("packages/utils")).unwrap();
+        fs::write(tmp.join("packages/utils/package.json"), "{}").unwrap();
+
+        let info = ProjectInfo::detect(&tmp, RootStrategy::Git);
+        assert!(!info.has_react);
+        assert_eq!(info.packages.len(), 2);
+        let web = &info.packages[0];
+        assert_eq!(web.name, "@acme/web");
+        assert_eq!(web.dir, PathBuf::from("apps/web"));
+        assert_eq!(web.info.root, tmp.join("apps/web"));
+        assert!(web.info.has_react && web.info.has_tsconfig);
+        let utils = &info.packages[1];
+        assert_eq!(utils.name, "packages/utils");
+        assert!(!utils.info.has_tsconfig);
+    }
+
+    #[test]
+    fn single_package_repo_has_no_packages() {
+        let tmp = TempDir::new("project-single");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("package.json"), "{}").unwrap();
+
+        assert!(
+            ProjectInfo::detect(&tmp, RootStrategy::Git)
+                .packages
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn nearest_package_root_stops_at_closest_manifest() {
+        let tmp = TempDir::new("project-nearest");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::write(tmp.join("package.json"), r#"{"workspaces": ["apps/*"]}"#).unwrap();
+        let web = tmp.join("apps/web");
+        fs::create_dir_all(web.join("src/components")).unwrap();
+        fs::write(
+            web.join("package.json"),
+            r#"{"dependencies": {"react": "^19"}}"#,
+        )
+        .unwrap();
+
+        let info = ProjectInfo::detect(&web.join("src/components"), RootStrategy::NearestPackage);
+        assert_eq!(info.root, web);
+        assert!(info.has_react);
+        assert!(info.packages.is_empty());
+
+        let git = ProjectInfo::detect(&web.join("src/components"), RootStrategy::Git);
+        assert_eq!(git.root, tmp.to_path_buf());
+    }
+
+    #[test]
+    fn nearest_package_falls_back_to_git_root() {
+        let tmp = TempDir::new("project-nearest-fallback");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        fs::create_dir_all(tmp.join("src")).unwrap();
+
+        let info = ProjectInfo::detect(&tmp.join("src"), RootStrategy::NearestPackage);
+        assert_eq!(info.root, tmp.to_path_buf());
+    }
 }
@@ -0,0 +1,158 @@
+use crate::diagnostic;
+use crate::tools::ToolResult;
+use serde_json::json;
+
+/// Aggregated, human-readable view of a run: a one-line `reason` and the
+/// markdown `context` handed to the agent.
+#[derive(Debug)]
+pub struct Report {
+    pub reason: String,
+    pub context: String,
+}
+
+/// Renders one tool's section: its diagnostics when parsed, otherwise the raw
+/// output. Returns `None` when the tool has nothing to report.
+fn render_section(result: &ToolResult) -> Option<String> {
+    match &result.diagnostics {
+        Some(diagnostics) if diagnostics.is_empty() => None,
+        Some(diagnostics) => {
+            let mut section = format!(
+                "## {} ({})\n\n",
+                result.name,
+                diagnostic::summarize(diagnostics)
+            );
+            for d in diagnostics {
+                section.push_str(&format!("- {}\n", d));
+            }
+            section.push('\n');
+            Some(section)
+        }
+        None if result.output.is_empty() => None,
+        None => Some(format!(
+            "## {}\n\n``````\n{}\n``````\n\n",
+            result.name, result.output
+        )),
+    }
+}
+
+/// Whether `result` has diagnostics or unparsed output to show.
+pub fn has_findings(result: &ToolResult) -> bool {
+    match &result.diagnostics {
+        Some(diagnostics) => !diagnostics.is_empty(),
+        None => !result.output.is_empty(),
+    }
+}
+
+/// Returns `None` when no tool ran.
+pub fn render(results: &[ToolResult], baselined: usize) -> Option<Report> {
+    if results.is_empty() {
+        return None;
+    }
+
+    let sections: Vec<_> = results
+        .iter()
+        .filter_map(|r| render_section(r).map(|s| (r, s)))
+        .collect();
+
+    let mut context = String::from("# Pre-flight Analysis Results\n\n");
+    for (_, section) in &sections {
+        context.push_str(section);
+    }
+
+    let with_issues = sections.iter().filter(|(r, _)| !r.success).count();
+    let mut reason = if with_issues > 0 {
+        format!(
+            "Pre-flight: {}/{} tools reported ({} with issues)",
+            sections.len(),
+            results.len(),
+            with_issues
+        )
+    } else {
+        format!(
+            "Pre-flight: {}/{} tools reported",
+            sections.len(),
+            results.len()
+        )
+    };
+    if baselined > 0 {
+        reason.push_str(&format!(", {baselined} baselined findings hidden"));
+    }
+
+    Some(Report {
+        reason,
+        context: context.trim_end().to_string(),
+    })
+}
+
+/// Machine-readable form of the same results, one entry per tool. Tools
+/// without parsed diagnostics carry their raw `output` instead.
+pub fn to_json(results: &[ToolResult], baselined: usize) -> serde_json::Value {
+    let tools: Vec<_> = results
+        .iter()
+        .map(|r| {
+            let mut tool = json!({ "name": r.name, "success": r.success });
+            match &r.diagnostics {
+                Some(diagnostics) => tool["diagnostics"] = json!(diagnostics),
+                None => tool["output"] = json!(r.output),
+            }
+            tool
+        })
+        .collect();
+    json!({
+        "reason": render(results, baselined).map(|r| r.reason),
+        "baselined": baselined,
+        "tools": tools,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::{Diagnostic, Severity};
+
+    #[test]
+    fn to_json_separates_parsed_and_raw() {
+        let results = vec![
+            ToolResult {
+                name: "tsgo".into(),
+                output: String::new(),
+                success: false,
+                diagnostics: Some(vec![Diagnostic {
+                    tool: "tsgo".into(),
+                    severity: Severity::Error,
+                    file: Some("src/a.ts".into()),
+                    line: Some(2),
+                    message: "bad".into(),
+                    ..Default::default()
+                }]),
+            },
+            ToolResult {
+                name: "react-doctor".into(),
+                output: "raw text".into(),
+                success: true,
+                diagnostics: None,
+            },
+        ];
+        let json = to_json(&results, 3);
+        assert_eq!(json["baselined"], 3);
+        let tsgo = &json["tools"][0];
+        assert_eq!(tsgo["diagnostics"][0]["severity"], "error");
+        assert_eq!(tsgo["diagnostics"][0]["file"], "src/a.ts");
+        assert!(tsgo["diagnostics"][0].get("rule").is_none());
+        assert_eq!(json["tools"][1]["output"], "raw text");
+        assert!(json["reason"].as_str().unwrap().contains("2/2"));
+    }
+
+    #[test]
+    fn render_skips_clean_tools() {
+        let results = vec![ToolResult {
+            name: "oxlint".into(),
+            output: "{}".into(),
+            success: true,
+            diagnostics: Some(vec![]),
+        }];
+        let report = render(&results, 0).unwrap();
+        assert_eq!(report.reason, "Pre-flight: 0/1 tools reported");
+        assert_eq!(report.context, "# Pre-flight Analysis Results");
+    }
+}
@@ -1,5 +1,10 @@
+use std::ffi::OsString;
+use std::io::Read;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
 
 fn is_executable(path: &Path) -> bool {
     path.metadata()
@@ -7,21 +12,132 @@ fn is_executable(path: &Path) -> bool {
         .unwrap_or(false)
 }
 
-pub fn resolve_bin(name: &str, start: &Path) -> PathBuf {
+/// Project-local binary directories, checked in order at each ancestor.
+const LOCAL_BIN_DIRS: &[&str] = &["node_modules/.bin", ".venv/bin", "venv/bin"];
+
+/// Go adapters whose binaries are usually installed with `go install`. Only
+/// these are looked up in `GO_BIN_DIRS`, so other missing tools never spawn
+/// `go`.
+const GO_INSTALLED: &[&str] = &["staticcheck", "golangci-lint"];
+
+/// Bounds `go env GOPATH`, which may otherwise wait on a toolchain download.
+const GO_ENV_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Where `go install` puts binaries, which is often not on `$PATH`.
+static GO_BIN_DIRS: LazyLock<Vec<PathBuf>> = LazyLock::new(|| {
+    let gopath = std::env::var_os("GOPATH")
+        .filter(|v| !v.is_empty())
+        .or_else(go_env_gopath);
+    go_bin_dirs(std::env::var_os("GOBIN"), gopath)
+});
+
+/// Where a tool binary was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// In one of `LOCAL_BIN_DIRS`.
+    Local(PathBuf),
+    Path(PathBuf),
+    Missing,
+}
+
+fn find_local(name: &str, start: &Path) -> Option<PathBuf> {
     debug_assert!(
         !name.contains('/') && !name.contains('\\') && !name.contains(".."),
         "binary name must not contain path components: {name}"
     );
     crate::traverse::walk_ancestors(start, |dir| {
-        let candidate = dir.join("node_modules/.bin").join(name);
-        if candidate.exists() && is_executable(&candidate) {
+        LOCAL_BIN_DIRS
+            .iter()
+            .map(|bin_dir| dir.join(bin_dir).join(name))
+            .find(|candidate| candidate.exists() && is_executable(candidate))
+    })
+}
+
+fn find_in_path(name: &str) -> Option<PathBuf> {
+    let path = std::env::var_os("PATH")?;
+    let dirs: Vec<PathBuf> = std::env::split_paths(&path).collect();
+    find_in_dirs(name, &dirs)
+}
+
+fn find_in_dirs(name: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter()
+        .map(|dir| dir.join(name))
+        .find(|candidate| candidate.is_file() && is_executable(candidate))
+}
+
+/// `$GOBIN` when set, otherwise `bin` under each `GOPATH` entry.
+fn go_bin_dirs(gobin: Option<OsString>, gopath: Option<OsString>) -> Vec<PathBuf> {
+    if let Some(gobin) = gobin.filter(|v| !v.is_empty()) {
+        return vec![PathBuf::from(gobin)];
+    }
+    gopath
+        .map(|p| {
+            std::env::split_paths(&p)
+                .map(|dir| dir.join("bin"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// `go env GOPATH`, which falls back to `~/go` when the variable is unset.
+/// `GOTOOLCHAIN=local` keeps `go` from fetching the toolchain a `go.mod`
+/// asks for, and the call is abandoned after `GO_ENV_TIMEOUT`.
+fn go_env_gopath() -> Option<OsString> {
+    let mut child = Command::new("go")
+        .args(["env", "GOPATH"])
+        .env("GOTOOLCHAIN", "local")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let deadline = Instant::now() + GO_ENV_TIMEOUT;
+    let status = loop {
+        match child.try_wait().ok()? {
+            Some(status) => break status,
+            None if Instant::now() >= deadline => {
+                eprintln!("reviews: warning: `go env GOPATH` timed out, skipping GOPATH lookup");
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            None => std::thread::sleep(Duration::from_millis(10)),
+        }
+    };
+    let mut gopath = String::new();
+    child.stdout.take()?.read_to_string(&mut gopath).ok()?;
+    let gopath = gopath.trim();
+    (status.success() && !gopath.is_empty()).then(|| gopath.into())
+}
+
+/// A Go tool installed with `go install` but missing from `$PATH`.
+fn find_in_go_bin(name: &str) -> Option<PathBuf> {
+    if !GO_INSTALLED.contains(&name) || find_in_path(name).is_some() {
+        return None;
+    }
+    find_in_dirs(name, &GO_BIN_DIRS)
+}
+
+pub fn resolve_bin(name: &str, start: &Path) -> PathBuf {
+    match find_local(name, start).or_else(|| find_in_go_bin(name)) {
+        Some(candidate) => {
             eprintln!("reviews: resolved {} -> {}", name, candidate.display());
-            Some(candidate)
-        } else {
-            None
+            candidate
         }
-    })
-    .unwrap_or_else(|| PathBuf::from(name))
+        None => PathBuf::from(name),
+    }
+}
+
+/// Like `resolve_bin`, but also looks the bare name up in `$PATH` so callers
+/// can tell a missing tool from one that will be spawned from `$PATH`.
+pub fn locate(name: &str, start: &Path) -> Resolution {
+    if let Some(path) = find_local(name, start) {
+        Resolution::Local(path)
+    } else if let Some(path) = find_in_path(name).or_else(|| find_in_go_bin(name)) {
+        Resolution::Path(path)
+    } else {
+        Resolution::Missing
+    }
 }
 
 #[cfg(test)]
@@ -82,4 +198,77 @@ mod tests {
         let result = resolve_bin("knip", &subdir);
         assert_eq!(result, PathBuf::from("knip"));
     }
+
+    #[test]
+    fn finds_bin_in_virtualenv() {
+        let tmp = TempDir::new("resolve-venv");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        let bin_dir = tmp.join(".venv/bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        let bin_path = bin_dir.join("ruff");
+        fs::write(&bin_path, "").unwrap();
+        fs::set_permissions(&bin_path, fs::Permissions::from_mode(0o755)).unwrap();
+        let subdir = tmp.join("app");
+        fs::create_dir_all(&subdir).unwrap();
+
+        assert_eq!(resolve_bin("ruff", &subdir), bin_path);
+    }
+
+    #[test]
+    fn go_bin_dirs_prefer_gobin() {
+        assert_eq!(
+            go_bin_dirs(Some("/opt/gobin".into()), Some("/home/u/go".into())),
+            vec![PathBuf::from("/opt/gobin")]
+        );
+        assert_eq!(
+            go_bin_dirs(Some("".into()), Some("/home/u/go:/srv/go".into())),
+            vec![
+                PathBuf::from("/home/u/go/bin"),
+                PathBuf::from("/srv/go/bin")
+            ]
+        );
+        assert!(go_bin_dirs(None, None).is_empty());
+    }
+
+    #[test]
+    fn go_bin_lookup_is_limited_to_go_tools() {
+        for name in ["knip", "react-doctor", "eslint"] {
+            assert!(find_in_go_bin(name).is_none());
+        }
+        // Not forced: other tests may already have initialized it.
+        assert!(GO_INSTALLED.contains(&"staticcheck"));
+    }
+
+    #[test]
+    fn finds_bin_in_extra_dirs() {
+        let tmp = TempDir::new("resolve-dirs");
+        let bin_dir = tmp.join("go/bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        let bin_path = bin_dir.join("staticcheck");
+        fs::write(&bin_path, "").unwrap();
+        fs::set_permissions(&bin_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(
+            find_in_dirs("staticcheck", &[tmp.join("missing"), bin_dir]),
+            Some(bin_path)
+        );
+    }
+
+    #[test]
+    fn locate_distinguishes_sources() {
+        let tmp = TempDir::new("resolve-locate");
+        fs::create_dir_all(tmp.join(".git")).unwrap();
+        let bin_dir = tmp.join("node_modules/.bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        let bin_path = bin_dir.join("knip");
+        fs::write(&bin_path, "").unwrap();
+        fs::set_permissions(&bin_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+        assert_eq!(locate("knip", &tmp), Resolution::Local(bin_path));
+        assert!(matches!(locate("sh", &tmp), Resolution::Path(_)));
+        assert_eq!(
+            locate("nonexistent-command-12345", &tmp),
+            Resolution::Missing
+        );
+    }
 }
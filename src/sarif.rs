@@ -0,0 +1,293 @@
+use crate::baseline;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::tools::ToolResult;
+use serde_json::{Value, json};
+use std::path::Path;
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SRCROOT: &str = "%SRCROOT%";
+
+fn information_uri(tool: &str) -> Option<&'static str> {
+    match tool {
+        "knip" => Some("https://knip.dev"),
+        "oxlint" => Some("https://oxc.rs"),
+        "tsgo" => Some("https://github.com/microsoft/typescript-go"),
+        "react-doctor" => Some("https://github.com/millionco/react-doctor"),
+        "vue-tsc" => Some("https://github.com/vuejs/language-tools"),
+        "svelte-check" => Some("https://github.com/sveltejs/language-tools"),
+        "astro-check" => Some("https://docs.astro.build/en/reference/cli-reference/#astro-check"),
+        "eslint" => Some("https://eslint.org"),
+        "biome" => Some("https://biomejs.dev"),
+        "clippy" => Some("https://github.com/rust-lang/rust-clippy"),
+        "cargo-check" | "cargo-test" => Some("https://doc.rust-lang.org/cargo/"),
+        "cargo-machete" => Some("https://github.com/bnjbvr/cargo-machete"),
+        "cargo-audit" => Some("https://rustsec.org"),
+        "npm-audit" | "pnpm-audit" | "yarn-audit" => Some("https://github.com/advisories"),
+        "ruff" => Some("https://docs.astral.sh/ruff/"),
+        "mypy" => Some("https://mypy-lang.org"),
+        "pyright" => Some("https://github.com/microsoft/pyright"),
+        "go-vet" => Some("https://pkg.go.dev/cmd/vet"),
+        "staticcheck" => Some("https://staticcheck.dev"),
+        "golangci-lint" => Some("https://golangci-lint.run"),
+        "vitest" => Some("https://vitest.dev"),
+        "jest" => Some("https://jestjs.io"),
+        _ => None,
+    }
+}
+
+fn level(severity: Severity) -> &'static str {
+    match severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Info => "note",
+    }
+}
+
+/// Percent-encodes `path` for a URI, keeping `/` separators.
+fn encode(path: &Path) -> String {
+    let path = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::with_capacity(path.len());
+    for b in path.bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~/".contains(&b) {
+            uri.push(b as char);
+        } else {
+            uri.push_str(&format!("%{b:02X}"));
+        }
+    }
+    uri
+}
+
+/// Relative paths and absolute ones under `root` resolve against
+/// `%SRCROOT%`; other absolute paths become plain `file://` URIs.
+fn location(d: &Diagnostic, root: &Path) -> Option<Value> {
+    let file = d.file.as_deref()?;
+    let file = file.strip_prefix("./").unwrap_or(file);
+    let file = file.strip_prefix(root).unwrap_or(file);
+    let artifact = if file.is_absolute() {
+        json!({ "uri": format!("file://{}", encode(file)) })
+    } else {
+        json!({ "uri": encode(file), "uriBaseId": SRCROOT })
+    };
+    let mut physical = json!({ "artifactLocation": artifact });
+    if let Some(line) = d.line {
+        let mut region = json!({ "startLine": line });
+        for (key, value) in [
+            ("startColumn", d.column),
+            ("endLine", d.end_line),
+            ("endColumn", d.end_column),
+        ] {
+            if let Some(v) = value {
+                region[key] = json!(v);
+            }
+        }
+        physical["region"] = region;
+    }
+    Some(json!({ "physicalLocation": physical }))
+}
+
+fn run(result: &ToolResult, root: &Path) -> Value {
+    let diagnostics = result.diagnostics.as_deref().unwrap_or_default();
+
+    let mut rule_ids: Vec<&str> = diagnostics
+        .iter()
+        .filter_map(|d| d.rule.as_deref())
+        .collect();
+    rule_ids.sort_unstable();
+    rule_ids.dedup();
+    let rules: Vec<Value> = rule_ids
+        .iter()
+        .map(|id| json!({ "id": id, "name": id }))
+        .collect();
+
+    let mut results: Vec<Value> = diagnostics
+        .iter()
+        .map(|d| {
+            let mut r = json!({
+                "level": level(d.severity),
+                "message": { "text": d.message },
+                "locations": location(d, root).into_iter().collect::<Vec<_>>(),
+                "partialFingerprints": { "claudeReviews/v1": baseline::fingerprint(d) },
+            });
+            if let Some(rule) = &d.rule {
+                r["ruleId"] = json!(rule);
+                if let Ok(index) = rule_ids.binary_search(&rule.as_str()) {
+                    r["ruleIndex"] = json!(index);
+                }
+            }
+            if let Some(fix) = &d.fix {
+                r["message"]["markdown"] = json!(format!("{}\n\nFix: {}", d.message, fix));
+            }
+            r
+        })
+        .collect();
+
+    // Output no parser understood still reaches the agent, so keep it here.
+    if result.diagnostics.is_none() && !result.output.is_empty() {
+        results.push(json!({
+            "level": "note",
+            "message": { "text": result.output },
+            "locations": [],
+        }));
+    }
+
+    // Per-package runs are named like `tsgo (web)`.
+    let base_name = result.name.split(" (").next().unwrap_or(&result.name);
+    let mut driver = json!({ "name": result.name, "rules": rules });
+    if let Some(uri) = information_uri(base_name) {
+        driver["informationUri"] = json!(uri);
+    }
+    json!({
+        "tool": { "driver": driver },
+        "invocations": [{ "executionSuccessful": result.success }],
+        "results": results,
+    })
+}
+
+/// Builds a SARIF 2.1.0 log with one run per tool. Locations are relative to
+/// `root` through the `%SRCROOT%` base.
+pub fn log(results: &[ToolResult], root: &Path) -> Value {
+    let mut root_uri = format!("file://{}", encode(root));
+    if !root_uri.ends_with('/') {
+        root_uri.push('/');
+    }
+    let runs: Vec<Value> = results
+        .iter()
+        .map(|r| {
+            let mut run = run(r, root);
+            run["originalUriBaseIds"] = json!({ SRCROOT: { "uri": root_uri } });
+            run
+        })
+        .collect();
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": runs,
+    })
+}
+
+pub fn write(path: &Path, results: &[ToolResult], root: &Path) -> std::io::Result<()> {
+    let mut json =
+        serde_json::to_string_pretty(&log(results, root)).map_err(std::io::Error::other)?;
+    json.push('\n');
+    std::fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn results() -> Vec<ToolResult> {
+        vec![
+            ToolResult {
+                name: "tsgo".into(),
+                output: String::new(),
+                success: false,
+                diagnostics: Some(vec![
+                    Diagnostic {
+                        tool: "tsgo".into(),
+                        rule: Some("TS2322".into()),
+                        severity: Severity::Error,
+                        file: Some("src/a.ts".into()),
+                        line: Some(3),
+                        column: Some(7),
+                        message: "Type mismatch".into(),
+                        ..Default::default()
+                    },
+                    Diagnostic {
+                        tool: "tsgo".into(),
+                        rule: Some("TS5083".into()),
+                        severity: Severity::Error,
+                        message: "Cannot read file".into(),
+                        ..Default::default()
+                    },
+                ]),
+            },
+            ToolResult {
+                name: "react-doctor".into(),
+                output: "raw".into(),
+                success: true,
+                diagnostics: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn one_run_per_tool_with_rules() {
+        let log = log(&results(), &PathBuf::from("/repo"));
+        assert_eq!(log["version"], "2.1.0");
+        let runs = log["runs"].as_array().unwrap();
+        assert_eq!(runs.len(), 2);
+
+        let tsgo = &runs[0];
+        assert_eq!(tsgo["tool"]["driver"]["name"], "tsgo");
+        assert_eq!(tsgo["tool"]["driver"]["rules"][1]["id"], "TS5083");
+        assert_eq!(tsgo["originalUriBaseIds"][SRCROOT]["uri"], "file:///repo/");
+
+        let raw = runs[1]["results"].as_array().unwrap();
+        assert_eq!(raw.len(), 1);
+        assert_eq!(raw[0]["level"], "note");
+        assert_eq!(raw[0]["message"]["text"], "raw");
+    }
+
+    #[test]
+    fn per_package_runs_keep_tool_metadata() {
+        let mut results = results();
+        results[0].name = "tsgo (web)".into();
+        let log = log(&results, &PathBuf::from("/repo"));
+        let driver = &log["runs"][0]["tool"]["driver"];
+        assert_eq!(driver["name"], "tsgo (web)");
+        assert_eq!(
+            driver["informationUri"],
+            "https://github.com/microsoft/typescript-go"
+        );
+    }
+
+    #[test]
+    fn results_carry_physical_locations() {
+        let log = log(&results(), &PathBuf::from("/repo"));
+        let first = &log["runs"][0]["results"][0];
+        assert_eq!(first["ruleId"], "TS2322");
+        assert_eq!(first["ruleIndex"], 0);
+        assert_eq!(first["level"], "error");
+        let physical = &first["locations"][0]["physicalLocation"];
+        assert_eq!(physical["artifactLocation"]["uri"], "src/a.ts");
+        assert_eq!(physical["artifactLocation"]["uriBaseId"], SRCROOT);
+        assert_eq!(physical["region"]["startLine"], 3);
+        assert_eq!(physical["region"]["startColumn"], 7);
+        assert!(physical["region"].get("endLine").is_none());
+
+        let global = &log["runs"][0]["results"][1];
+        assert_eq!(global["locations"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn uris_are_encoded_and_outside_paths_are_absolute() {
+        let mut results = results();
+        let diagnostics = results[0].diagnostics.as_mut().unwrap();
+        diagnostics[0].file = Some("/my repo/src/a #1.ts".into());
+        diagnostics[1].file = Some("/usr/lib/node.d.ts".into());
+        let log = log(&results, &PathBuf::from("/my repo"));
+        let run = &log["runs"][0];
+        assert_eq!(
+            run["originalUriBaseIds"][SRCROOT]["uri"],
+            "file:///my%20repo/"
+        );
+
+        let inside = &run["results"][0]["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!(inside["uri"], "src/a%20%231.ts");
+        assert_eq!(inside["uriBaseId"], SRCROOT);
+        let outside = &run["results"][1]["locations"][0]["physicalLocation"]["artifactLocation"];
+        assert_eq!(outside["uri"], "file:///usr/lib/node.d.ts");
+        assert!(outside.get("uriBaseId").is_none());
+    }
+
+    #[test]
+    fn write_creates_file() {
+        let tmp = crate::test_utils::TempDir::new("sarif-write");
+        let path = tmp.join("out.sarif");
+        write(&path, &results(), &tmp).unwrap();
+        let parsed: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        assert_eq!(parsed["runs"].as_array().unwrap().len(), 2);
+    }
+}
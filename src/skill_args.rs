@@ -0,0 +1,189 @@
+use crate::config::{Config, Scope};
+use crate::tools::ToolResult;
+use std::path::{Path, PathBuf};
+
+/// Options passed to the skill itself, as in
+/// `/review src/components --tools oxlint,tsgo --diff main`. They override
+/// the config for that one invocation.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct SkillArgs {
+    /// Words that are not options; only those naming an existing path are
+    /// used, and the rest are ignored with a warning.
+    pub words: Vec<String>,
+    /// `--tools a,b`: run exactly these tools.
+    pub tools: Option<Vec<String>>,
+    /// `--diff [REF]`: report only changed lines, against `REF` when given.
+    pub diff: bool,
+    pub base: Option<String>,
+}
+
+impl SkillArgs {
+    /// Splits on whitespace. Unknown options are ignored.
+    pub fn parse(args: &str) -> Self {
+        let mut parsed = Self::default();
+        let mut iter = args.split_whitespace().peekable();
+        while let Some(arg) = iter.next() {
+            if let Some(value) = arg.strip_prefix("--tools=") {
+                parsed.tools = Some(tool_list(value));
+            } else if arg == "--tools" {
+                parsed.tools = iter.next().map(tool_list);
+            } else if let Some(value) = arg.strip_prefix("--diff=") {
+                parsed.diff = true;
+                parsed.base = Some(value.to_string());
+            } else if arg == "--diff" {
+                parsed.diff = true;
+                parsed.base = iter
+                    .next_if(|next| !next.starts_with('-'))
+                    .map(str::to_string);
+            } else if !arg.starts_with('-') {
+                parsed.words.push(arg.to_string());
+            }
+        }
+        parsed
+    }
+
+    /// Applies the arguments to `config`. Paths are resolved against `cwd`.
+    pub fn apply(self, config: &mut Config, cwd: &Path) {
+        config.paths = self
+            .words
+            .iter()
+            .filter_map(|word| {
+                let path = cwd.join(word);
+                if !path.exists() {
+                    eprintln!("reviews: warning: ignoring {word}: no such path");
+                    return None;
+                }
+                Some(path)
+            })
+            .collect();
+        if self.tools.is_some() {
+            config.only_tools = self.tools;
+        }
+        if self.diff {
+            if config.scope == Scope::Project {
+                config.scope = Scope::Diff;
+            }
+            if self.base.is_some() {
+                config.base = self.base;
+            }
+        }
+    }
+}
+
+fn tool_list(value: &str) -> Vec<String> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Drops parsed diagnostics outside `paths` (relative to the project root).
+/// Findings without a file and raw output are kept. Returns the number of
+/// diagnostics removed.
+pub fn filter_paths(results: &mut [ToolResult], paths: &[PathBuf]) -> usize {
+    let mut hidden = 0;
+    for diagnostics in results.iter_mut().filter_map(|r| r.diagnostics.as_mut()) {
+        let before = diagnostics.len();
+        diagnostics.retain(|d| {
+            d.file.as_ref().is_none_or(|file| {
+                let file = file.strip_prefix("./").unwrap_or(file);
+                paths.iter().any(|p| file.starts_with(p))
+            })
+        });
+        hidden += before - diagnostics.len();
+    }
+    hidden
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diagnostic::Diagnostic;
+    use crate::test_utils::TempDir;
+
+    #[test]
+    fn parses_paths_tools_and_diff_base() {
+        assert_eq!(
+            SkillArgs::parse("src/components --tools oxlint,tsgo --diff main"),
+            SkillArgs {
+                words: vec!["src/components".into()],
+                tools: Some(vec!["oxlint".into(), "tsgo".into()]),
+                diff: true,
+                base: Some("main".into()),
+            }
+        );
+        assert_eq!(
+            SkillArgs::parse("--tools=eslint, --diff=origin/main"),
+            SkillArgs {
+                tools: Some(vec!["eslint".into()]),
+                diff: true,
+                base: Some("origin/main".into()),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn bare_diff_and_unknown_options() {
+        assert_eq!(
+            SkillArgs::parse("--diff --verbose"),
+            SkillArgs {
+                diff: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(SkillArgs::parse(""), SkillArgs::default());
+    }
+
+    #[test]
+    fn apply_keeps_existing_paths_and_overrides_config() {
+        let tmp = TempDir::new("skill-args");
+        std::fs::create_dir_all(tmp.join("src/components")).unwrap();
+        let mut config = Config {
+            base: Some("develop".into()),
+            ..Default::default()
+        };
+        SkillArgs::parse("check src/components please --tools knip --diff main")
+            .apply(&mut config, &tmp);
+        assert_eq!(config.paths, vec![tmp.join("src/components")]);
+        assert_eq!(config.only_tools, Some(vec!["knip".to_string()]));
+        assert_eq!(config.scope, Scope::Diff);
+        assert_eq!(config.base.as_deref(), Some("main"));
+
+        let mut config = Config {
+            scope: Scope::Staged,
+            base: Some("develop".into()),
+            ..Default::default()
+        };
+        SkillArgs::parse("--diff").apply(&mut config, &tmp);
+        assert_eq!(config.scope, Scope::Staged);
+        assert_eq!(config.base.as_deref(), Some("develop"));
+        assert!(config.paths.is_empty());
+        assert!(config.only_tools.is_none());
+    }
+
+    #[test]
+    fn filter_paths_keeps_findings_under_paths() {
+        let diagnostic = |file: Option<&str>| Diagnostic {
+            file: file.map(PathBuf::from),
+            message: "m".into(),
+            ..Default::default()
+        };
+        let mut results = vec![ToolResult {
+            name: "oxlint".into(),
+            output: String::new(),
+            success: false,
+            diagnostics: Some(vec![
+                diagnostic(Some("src/components/a.ts")),
+                diagnostic(Some("./src/components/b.ts")),
+                diagnostic(Some("src/lib/c.ts")),
+                diagnostic(None),
+            ]),
+        }];
+        let hidden = filter_paths(&mut results, &[PathBuf::from("src/components")]);
+        assert_eq!(hidden, 1);
+        assert_eq!(results[0].diagnostics.as_ref().unwrap().len(), 3);
+    }
+}
@@ -0,0 +1,95 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `src/pages/index.astro:5:7 - error ts(2322): message`
+static LOCATED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?P<file>[^\s:][^:]*):(?P<line>\d+):(?P<col>\d+) - (?P<sev>error|warning|hint)(?: (?P<code>ts\((?P<num>\d+)\)|[\w/-]+))?: (?P<msg>.*)$",
+    )
+    .unwrap()
+});
+/// `Result (12 files): ` followed by the counts.
+static RESULT_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^Result \(\d+ files?\)").unwrap());
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_astro
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("astro-check");
+    }
+
+    let bin = resolve::resolve_bin("astro", &ctx.project.root);
+    super::run_command("astro-check", &bin, &["check"], &[], ctx, parse)
+}
+
+/// Parses `astro check`. TypeScript codes such as `ts(2322)` are reported
+/// as `TS2322`, matching tsgo; indented code frames are skipped.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut recognized = false;
+    let mut diagnostics = Vec::new();
+    for line in output.lines() {
+        if let Some(caps) = LOCATED_RE.captures(line) {
+            recognized = true;
+            let rule = match caps.name("num") {
+                Some(num) => Some(format!("TS{}", num.as_str())),
+                None => caps.name("code").map(|c| c.as_str().to_string()),
+            };
+            diagnostics.push(Diagnostic {
+                rule,
+                severity: Severity::from_label(&caps["sev"]),
+                file: Some(caps["file"].into()),
+                line: caps["line"].parse().ok(),
+                column: caps["col"].parse().ok(),
+                message: caps["msg"].to_string(),
+                ..Default::default()
+            });
+        } else if RESULT_RE.is_match(line) {
+            recognized = true;
+        }
+    }
+    recognized.then_some(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_check_output() {
+        let output = "11:02:41 [check] Getting diagnostics for Astro files in /repo...\n\
+src/pages/index.astro:5:7 - error ts(2322): Type 'number' is not assignable to type 'string'.\n\
+\n\
+5 const title: string = 1;\n\
+        ~~~~~\n\
+src/components/Card.astro:2:1 - hint: 'x' is declared but its value is never read.\n\
+\n\
+Result (12 files): \n\
+- 1 error\n\
+- 0 warnings\n\
+- 1 hint\n";
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].rule.as_deref(), Some("TS2322"));
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(
+            diags[0].file.as_deref(),
+            Some(Path::new("src/pages/index.astro"))
+        );
+        assert_eq!((diags[0].line, diags[0].column), (Some(5), Some(7)));
+        assert_eq!(diags[1].rule, None);
+        assert_eq!(diags[1].severity, Severity::Info);
+    }
+
+    #[test]
+    fn unrecognized_output() {
+        assert!(parse("Cannot find module '@astrojs/check'").is_none());
+    }
+}
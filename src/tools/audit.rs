@@ -0,0 +1,477 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+use std::path::Path;
+
+const SHRINKWRAP: &str = "npm-shrinkwrap.json";
+
+/// JavaScript package managers, in the order their lockfiles take precedence
+/// when several are present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Manager {
+    Pnpm,
+    Yarn,
+    Npm,
+}
+
+impl Manager {
+    fn detect(root: &Path) -> Option<Self> {
+        if root.join("pnpm-lock.yaml").is_file() {
+            Some(Self::Pnpm)
+        } else if root.join("yarn.lock").is_file() {
+            Some(Self::Yarn)
+        } else if root.join("package-lock.json").is_file() || root.join(SHRINKWRAP).is_file() {
+            Some(Self::Npm)
+        } else {
+            None
+        }
+    }
+}
+
+pub fn applies_cargo(project: &ProjectInfo) -> bool {
+    project.root.join("Cargo.lock").is_file()
+}
+
+pub fn applies_npm(project: &ProjectInfo) -> bool {
+    Manager::detect(&project.root) == Some(Manager::Npm)
+}
+
+pub fn applies_pnpm(project: &ProjectInfo) -> bool {
+    Manager::detect(&project.root) == Some(Manager::Pnpm)
+}
+
+pub fn applies_yarn(project: &ProjectInfo) -> bool {
+    Manager::detect(&project.root) == Some(Manager::Yarn)
+}
+
+/// Runs an audit and treats a failure without a parseable report (cache
+/// miss, missing advisory database) as skipped rather than dumping the
+/// tool's error.
+fn run_audit(
+    name: &'static str,
+    bin: &str,
+    args: &[&str],
+    ctx: &ToolContext,
+    parse: diagnostic::Parser,
+) -> ToolResult {
+    let bin = resolve::resolve_bin(bin, &ctx.project.root);
+    let result = super::run_command(name, &bin, args, &[], ctx, parse);
+    if result.diagnostics.is_none() && !result.success && !result.output.is_empty() {
+        eprintln!(
+            "reviews: warning: {name} produced no report (advisory data not cached locally), skipping"
+        );
+        return ToolResult::skipped(name);
+    }
+    result
+}
+
+/// Checks `Cargo.lock` against the locally cached RustSec database;
+/// `--no-fetch` keeps it from cloning the database over the network.
+pub fn run_cargo(ctx: &ToolContext) -> ToolResult {
+    if !applies_cargo(&ctx.project) {
+        return ToolResult::skipped("cargo-audit");
+    }
+
+    run_audit(
+        "cargo-audit",
+        "cargo",
+        &["audit", "--json", "--no-fetch", "--stale"],
+        ctx,
+        parse_cargo,
+    )
+}
+
+/// `--offline` answers from the local cache only. npm reads
+/// `npm-shrinkwrap.json` in preference to `package-lock.json`, so findings
+/// are anchored on whichever it used.
+pub fn run_npm(ctx: &ToolContext) -> ToolResult {
+    if !applies_npm(&ctx.project) {
+        return ToolResult::skipped("npm-audit");
+    }
+
+    let mut result = run_audit(
+        "npm-audit",
+        "npm",
+        &["audit", "--json", "--offline"],
+        ctx,
+        parse_npm,
+    );
+    if ctx.project.root.join(SHRINKWRAP).is_file() {
+        for d in result.diagnostics.iter_mut().flatten() {
+            d.file = Some(SHRINKWRAP.into());
+        }
+    }
+    result
+}
+
+pub fn run_pnpm(ctx: &ToolContext) -> ToolResult {
+    if !applies_pnpm(&ctx.project) {
+        return ToolResult::skipped("pnpm-audit");
+    }
+
+    run_audit(
+        "pnpm-audit",
+        "pnpm",
+        &["audit", "--json", "--offline"],
+        ctx,
+        parse_pnpm,
+    )
+}
+
+/// `yarn npm audit` has no offline flag; disabling the network makes it fail
+/// fast instead of waiting on the registry.
+pub fn run_yarn(ctx: &ToolContext) -> ToolResult {
+    if !applies_yarn(&ctx.project) {
+        return ToolResult::skipped("yarn-audit");
+    }
+
+    let mut ctx = ctx.clone();
+    ctx.settings
+        .env
+        .entry("YARN_ENABLE_NETWORK".into())
+        .or_insert_with(|| "0".into());
+    run_audit(
+        "yarn-audit",
+        "yarn",
+        &["npm", "audit", "--json", "--all", "--recursive"],
+        &ctx,
+        parse_yarn,
+    )
+}
+
+/// Builds one finding per advisory, anchored to the lockfile.
+fn advisory(
+    lockfile: &str,
+    id: Option<String>,
+    severity: Severity,
+    package: &str,
+    title: &str,
+    fix: Option<String>,
+) -> Diagnostic {
+    Diagnostic {
+        rule: id,
+        severity,
+        file: Some(lockfile.into()),
+        message: format!("{package}: {title}"),
+        fix: Some(fix.unwrap_or_else(|| "no fixed version available".into())),
+        ..Default::default()
+    }
+}
+
+/// Advisory ID from a GitHub advisory URL, e.g. `GHSA-jf85-cpcp-j695`.
+fn advisory_id(url: Option<&str>) -> Option<String> {
+    url.and_then(|u| u.rsplit('/').next())
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
+/// Parses `cargo audit --json`. Vulnerabilities are errors; informational
+/// warnings (unmaintained, unsound, yanked) are warnings.
+pub fn parse_cargo(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let list = json.get("vulnerabilities")?.get("list")?.as_array()?;
+
+    let mut diagnostics: Vec<Diagnostic> = list
+        .iter()
+        .map(|v| cargo_entry(v, Severity::Error))
+        .collect();
+    if let Some(warnings) = json.get("warnings").and_then(|w| w.as_object()) {
+        diagnostics.extend(
+            warnings
+                .values()
+                .filter_map(|w| w.as_array())
+                .flatten()
+                .map(|w| cargo_entry(w, Severity::Warning)),
+        );
+    }
+    Some(diagnostics)
+}
+
+fn cargo_entry(entry: &Value, severity: Severity) -> Diagnostic {
+    let advisory_json = entry.get("advisory");
+    let package = entry.get("package");
+    let name = package.and_then(|p| p.get("name")).and_then(|n| n.as_str());
+    let version = package
+        .and_then(|p| p.get("version"))
+        .and_then(|v| v.as_str());
+    let title = advisory_json
+        .and_then(|a| a.get("title"))
+        .and_then(|t| t.as_str())
+        .or_else(|| entry.get("kind").and_then(|k| k.as_str()))
+        .unwrap_or("advisory");
+    let patched: Vec<&str> = entry
+        .get("versions")
+        .and_then(|v| v.get("patched"))
+        .and_then(|p| p.as_array())
+        .map(|p| p.iter().filter_map(|v| v.as_str()).collect())
+        .unwrap_or_default();
+    advisory(
+        "Cargo.lock",
+        advisory_json.and_then(|a| diagnostic::json_string(a, "id")),
+        severity,
+        format!("{} {}", name.unwrap_or("?"), version.unwrap_or("")).trim_end(),
+        title,
+        (!patched.is_empty()).then(|| format!("upgrade to {}", patched.join(" or "))),
+    )
+}
+
+/// Parses `npm audit --json` (report version 2). Only packages with their own
+/// advisories are listed; packages vulnerable through a dependency are
+/// covered by that dependency's entry.
+pub fn parse_npm(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let vulnerabilities = json.get("vulnerabilities")?.as_object()?;
+
+    let mut diagnostics = Vec::new();
+    for (name, vuln) in vulnerabilities {
+        let fix = match vuln.get("fixAvailable") {
+            Some(Value::Object(fix)) => {
+                let package = fix.get("name").and_then(|n| n.as_str()).unwrap_or(name);
+                let version = fix.get("version").and_then(|v| v.as_str()).unwrap_or("");
+                let major = fix.get("isSemVerMajor").and_then(|m| m.as_bool()) == Some(true);
+                let note = if major { " (semver-major)" } else { "" };
+                Some(format!("upgrade {package} to {version}{note}"))
+            }
+            Some(Value::Bool(true)) => Some("run `npm audit fix`".into()),
+            _ => None,
+        };
+        let sources = vuln.get("via").and_then(|v| v.as_array());
+        for source in sources.into_iter().flatten().filter(|s| s.is_object()) {
+            let severity = source.get("severity").and_then(|s| s.as_str());
+            diagnostics.push(advisory(
+                "package-lock.json",
+                advisory_id(source.get("url").and_then(|u| u.as_str())),
+                Severity::from_label(severity.unwrap_or("")),
+                name,
+                source
+                    .get("title")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or("advisory"),
+                fix.clone(),
+            ));
+        }
+    }
+    Some(diagnostics)
+}
+
+/// Parses `pnpm audit --json`, which uses the npm v6 `advisories` map.
+pub fn parse_pnpm(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let advisories = json.get("advisories")?.as_object()?;
+
+    Some(
+        advisories
+            .values()
+            .map(|a| {
+                let name = a.get("module_name").and_then(|n| n.as_str());
+                let patched = a.get("patched_versions").and_then(|p| p.as_str());
+                advisory(
+                    "pnpm-lock.yaml",
+                    diagnostic::json_string(a, "github_advisory_id")
+                        .or_else(|| advisory_id(a.get("url").and_then(|u| u.as_str()))),
+                    Severity::from_label(a.get("severity").and_then(|s| s.as_str()).unwrap_or("")),
+                    name.unwrap_or("?"),
+                    a.get("title")
+                        .and_then(|t| t.as_str())
+                        .unwrap_or("advisory"),
+                    patched
+                        .filter(|p| *p != "<0.0.0")
+                        .map(|p| format!("upgrade to {p}")),
+                )
+            })
+            .collect(),
+    )
+}
+
+/// Parses `yarn npm audit --json`: one JSON object per line, each with the
+/// package in `value` and the advisory in `children`.
+pub fn parse_yarn(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut recognized = false;
+    let mut diagnostics = Vec::new();
+    for line in output.lines() {
+        let Ok(json) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let (Some(name), Some(children)) = (
+            json.get("value").and_then(|v| v.as_str()),
+            json.get("children"),
+        ) else {
+            continue;
+        };
+        recognized = true;
+        let vulnerable = children.get("Vulnerable Versions").and_then(|v| v.as_str());
+        diagnostics.push(advisory(
+            "yarn.lock",
+            advisory_id(children.get("URL").and_then(|u| u.as_str())),
+            Severity::from_label(
+                children
+                    .get("Severity")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or(""),
+            ),
+            name,
+            children
+                .get("Issue")
+                .and_then(|i| i.as_str())
+                .unwrap_or("advisory"),
+            vulnerable.map(|v| format!("upgrade to a version outside {v}")),
+        ));
+    }
+    // A clean audit prints nothing (or a non-JSON summary line).
+    (recognized || output.trim().is_empty()).then_some(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::fs;
+
+    #[test]
+    fn lockfile_selects_package_manager() {
+        let tmp = TempDir::new("audit-lockfile");
+        let project = ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        };
+        assert!(!applies_npm(&project) && !applies_cargo(&project));
+
+        fs::write(tmp.join("package-lock.json"), "{}").unwrap();
+        assert!(applies_npm(&project));
+        fs::write(tmp.join("pnpm-lock.yaml"), "").unwrap();
+        assert!(applies_pnpm(&project) && !applies_npm(&project));
+
+        fs::write(tmp.join("Cargo.lock"), "").unwrap();
+        assert!(applies_cargo(&project));
+    }
+
+    #[test]
+    fn parses_cargo_audit() {
+        let output = r#"{
+            "database": {"advisory-count": 600},
+            "vulnerabilities": {"found": true, "count": 1, "list": [{
+                "advisory": {"id": "RUSTSEC-2020-0071", "package": "time", "title": "Potential segfault in the time crate"},
+                "versions": {"patched": [">=0.2.23"], "unaffected": ["=0.2.0"]},
+                "package": {"name": "time", "version": "0.1.45"}
+            }]},
+            "warnings": {"unmaintained": [{
+                "kind": "unmaintained",
+                "package": {"name": "ansi_term", "version": "0.12.1"},
+                "advisory": {"id": "RUSTSEC-2021-0139", "title": "ansi_term is Unmaintained"},
+                "versions": {"patched": []}
+            }]}
+        }"#;
+        let diags = parse_cargo(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].rule.as_deref(), Some("RUSTSEC-2020-0071"));
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(
+            diags[0].message,
+            "time 0.1.45: Potential segfault in the time crate"
+        );
+        assert_eq!(diags[0].fix.as_deref(), Some("upgrade to >=0.2.23"));
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("Cargo.lock")));
+        assert_eq!(diags[1].severity, Severity::Warning);
+        assert_eq!(diags[1].fix.as_deref(), Some("no fixed version available"));
+    }
+
+    #[test]
+    fn parses_npm_audit() {
+        let output = r#"{
+            "auditReportVersion": 2,
+            "vulnerabilities": {
+                "lodash": {
+                    "name": "lodash", "severity": "high",
+                    "via": [{"source": 1094, "name": "lodash", "title": "Prototype Pollution in lodash",
+                             "url": "https://github.com/advisories/GHSA-jf85-cpcp-j695", "severity": "critical"}],
+                    "fixAvailable": {"name": "lodash", "version": "4.17.21", "isSemVerMajor": false}
+                },
+                "webpack": {"name": "webpack", "severity": "high", "via": ["lodash"], "fixAvailable": true}
+            },
+            "metadata": {}
+        }"#;
+        let diags = parse_npm(output).unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].rule.as_deref(), Some("GHSA-jf85-cpcp-j695"));
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(diags[0].message, "lodash: Prototype Pollution in lodash");
+        assert_eq!(diags[0].fix.as_deref(), Some("upgrade lodash to 4.17.21"));
+    }
+
+    #[test]
+    fn parses_pnpm_audit() {
+        let output = r#"{"advisories": {"1094": {
+            "module_name": "minimist", "severity": "moderate", "title": "Prototype Pollution",
+            "github_advisory_id": "GHSA-vh95-rmgr-6w4m", "patched_versions": ">=1.2.6"
+        }}, "metadata": {}}"#;
+        let diags = parse_pnpm(output).unwrap();
+        assert_eq!(diags[0].rule.as_deref(), Some("GHSA-vh95-rmgr-6w4m"));
+        assert_eq!(diags[0].severity, Severity::Warning);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("pnpm-lock.yaml")));
+        assert_eq!(diags[0].fix.as_deref(), Some("upgrade to >=1.2.6"));
+    }
+
+    #[test]
+    fn parses_yarn_audit() {
+        let output = r#"{"value":"minimist","children":{"ID":1179,"Issue":"Prototype Pollution","URL":"https://github.com/advisories/GHSA-xvch-5gv4-984h","Severity":"critical","Vulnerable Versions":"<1.2.6","Tree Versions":["1.2.5"]}}"#;
+        let diags = parse_yarn(output).unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].rule.as_deref(), Some("GHSA-xvch-5gv4-984h"));
+        assert_eq!(
+            diags[0].fix.as_deref(),
+            Some("upgrade to a version outside <1.2.6")
+        );
+        assert_eq!(parse_yarn(""), Some(vec![]));
+    }
+
+    fn npm_project(name: &str, script: &str) -> (TempDir, ToolContext) {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = TempDir::new(name);
+        let bin_dir = tmp.join("node_modules/.bin");
+        fs::create_dir_all(&bin_dir).unwrap();
+        fs::write(bin_dir.join("npm"), script).unwrap();
+        fs::set_permissions(bin_dir.join("npm"), fs::Permissions::from_mode(0o755)).unwrap();
+        let ctx = ToolContext::new(ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        });
+        (tmp, ctx)
+    }
+
+    #[test]
+    fn offline_cache_miss_is_skipped() {
+        // Answers like npm: a cache miss under `--offline`, a clean report
+        // otherwise.
+        let (tmp, ctx) = npm_project(
+            "audit-offline",
+            "#!/bin/sh\ncase \"$*\" in *--offline*)\n  echo '{\"error\": {\"code\": \"ENOTCACHED\"}}'\n  exit 1;;\nesac\necho '{\"vulnerabilities\": {}}'\n",
+        );
+        fs::write(tmp.join("package-lock.json"), "{}").unwrap();
+        let result = run_npm(&ctx);
+        assert!(!result.success);
+        assert!(result.output.is_empty());
+        assert!(result.diagnostics.is_none());
+    }
+
+    #[test]
+    fn npm_findings_anchor_on_shrinkwrap() {
+        let report = r#"{"vulnerabilities": {"lodash": {"via": [{"title": "Prototype Pollution", "severity": "high"}]}}}"#;
+        let (tmp, ctx) = npm_project(
+            "audit-shrinkwrap",
+            &format!("#!/bin/sh\necho '{report}'\nexit 1\n"),
+        );
+        fs::write(tmp.join("npm-shrinkwrap.json"), "{}").unwrap();
+        let diags = run_npm(&ctx).diagnostics.unwrap();
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new(SHRINKWRAP)));
+    }
+
+    #[test]
+    fn unrecognized_output_falls_back() {
+        assert!(parse_cargo("error: couldn't open advisory database").is_none());
+        assert!(parse_npm(r#"{"error": {"code": "ENOTCACHED"}}"#).is_none());
+        assert!(parse_yarn("Internal Error: network disabled").is_none());
+    }
+}
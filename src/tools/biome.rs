@@ -0,0 +1,150 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+use std::path::PathBuf;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_biome_config
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("biome");
+    }
+    let files = match ctx.js_files() {
+        Some(files) if files.is_empty() => return ToolResult::skipped("biome"),
+        Some(files) => files,
+        None => vec![PathBuf::from(".")],
+    };
+
+    let bin = resolve::resolve_bin("biome", &ctx.project.root);
+    super::run_command(
+        "biome",
+        &bin,
+        &["check", "--reporter=json"],
+        &files,
+        ctx,
+        parse,
+    )
+}
+
+/// Parses `biome check --reporter=json`. Lint diagnostics keep their
+/// category (e.g. `lint/suspicious/noDebugger`) as the rule; formatting and
+/// import-sorting drift become one finding per file that would change.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    // Biome may print a notice after the single-line JSON document.
+    let json: Value = serde_json::from_str(output.trim()).ok().or_else(|| {
+        let line = output.lines().find(|l| l.starts_with('{'))?;
+        serde_json::from_str(line).ok()
+    })?;
+    let items = json.get("diagnostics")?.as_array()?;
+    Some(items.iter().filter_map(parse_item).collect())
+}
+
+fn parse_item(item: &Value) -> Option<Diagnostic> {
+    let category = diagnostic::json_string(item, "category");
+    let location = item.get("location");
+    // `{"file": "src/a.ts"}` in Biome 1.x, a bare string in 2.x.
+    let path = location.and_then(|l| l.get("path")).and_then(|p| {
+        p.as_str()
+            .map(str::to_string)
+            .or_else(|| diagnostic::json_string(p, "file"))
+    });
+    let (line, column) = location
+        .and_then(|l| {
+            let offset = l.get("span")?.as_array()?.first()?.as_u64()?;
+            let source = l.get("sourceCode")?.as_str()?;
+            line_column(source, usize::try_from(offset).ok()?)
+        })
+        .unzip();
+
+    let drift = category
+        .as_deref()
+        .is_some_and(|c| c == "format" || c.ends_with("organizeImports"));
+    let (message, fix) = if drift {
+        let fix = if category.as_deref() == Some("format") {
+            "run `biome format --write`"
+        } else {
+            "run `biome check --write`"
+        };
+        let message = if category.as_deref() == Some("format") {
+            "File would be reformatted".to_string()
+        } else {
+            "Imports would be sorted".to_string()
+        };
+        (message, Some(fix.to_string()))
+    } else {
+        let fixable = item
+            .get("tags")
+            .and_then(|t| t.as_array())
+            .is_some_and(|t| t.iter().any(|t| t.as_str() == Some("fixable")));
+        (
+            diagnostic::json_string(item, "description")?,
+            fixable.then(|| "auto-fixable with `biome check --write`".to_string()),
+        )
+    };
+    Some(Diagnostic {
+        rule: category,
+        severity: item
+            .get("severity")
+            .and_then(|s| s.as_str())
+            .map_or(Severity::Warning, Severity::from_label),
+        file: path.map(Into::into),
+        line: line.filter(|_| !drift),
+        column: column.filter(|_| !drift),
+        message,
+        fix,
+        ..Default::default()
+    })
+}
+
+/// 1-based line and column of a byte offset into `source`.
+fn line_column(source: &str, offset: usize) -> Option<(u32, u32)> {
+    let before = source.get(..offset)?;
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().map_or(0, |l| l.chars().count()) + 1;
+    Some((u32::try_from(line).ok()?, u32::try_from(column).ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_lint_and_format_diagnostics() {
+        let output = r#"{"summary":{"changed":0,"unchanged":2,"errors":2,"warnings":1},"diagnostics":[
+{"category":"lint/suspicious/noDebugger","severity":"error","description":"This is an unexpected use of the debugger statement.","tags":["fixable"],"location":{"path":{"file":"src/a.ts"},"span":[15,23],"sourceCode":"const a = 1;\n  debugger;\n"}},
+{"category":"lint/style/useConst","severity":"warning","description":"This let declares a variable that is only assigned once.","location":{"path":"src/c.ts","span":null}},
+{"category":"format","severity":"error","description":"File content differs from formatting output","location":{"path":{"file":"src/b.ts"},"span":null}}
+],"command":"check"}"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 3);
+        assert_eq!(diags[0].rule.as_deref(), Some("lint/suspicious/noDebugger"));
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!((diags[0].line, diags[0].column), (Some(2), Some(3)));
+        assert_eq!(
+            diags[0].fix.as_deref(),
+            Some("auto-fixable with `biome check --write`")
+        );
+        assert_eq!(diags[1].file.as_deref(), Some(Path::new("src/c.ts")));
+        assert_eq!(diags[1].line, None);
+        assert_eq!(diags[2].rule.as_deref(), Some("format"));
+        assert_eq!(diags[2].message, "File would be reformatted");
+        assert_eq!(diags[2].file.as_deref(), Some(Path::new("src/b.ts")));
+    }
+
+    #[test]
+    fn rejects_non_json() {
+        assert!(parse("The configuration file is invalid").is_none());
+    }
+
+    #[test]
+    fn converts_offsets() {
+        assert_eq!(line_column("ab\ncd", 4), Some((2, 2)));
+        assert_eq!(line_column("ab", 0), Some((1, 1)));
+        assert_eq!(line_column("ab", 9), None);
+    }
+}
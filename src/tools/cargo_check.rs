@@ -0,0 +1,178 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use regex::Regex;
+use serde_json::Value;
+use std::path::Path;
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Cold builds compile every dependency, which rarely fits the usual 60s.
+const CARGO_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Trailing rustc summaries such as `aborting due to 2 previous errors` or
+/// `` `crate` (lib) generated 3 warnings ``.
+static SUMMARY_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(aborting due to|\d+ warnings? emitted|.* generated \d+ warnings?)").unwrap()
+});
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_cargo_toml
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("cargo-check");
+    }
+
+    run_cargo("cargo-check", &["check", "--message-format=json"], ctx)
+}
+
+/// Runs a compiling cargo subcommand with `CARGO_TIMEOUT` unless the tool
+/// sets its own timeout. Shared by the clippy, check and test adapters.
+pub fn run_cargo(name: &'static str, args: &[&str], ctx: &ToolContext) -> ToolResult {
+    let mut ctx = ctx.clone();
+    ctx.settings.timeout.get_or_insert(CARGO_TIMEOUT);
+    super::run_command(name, Path::new("cargo"), args, &[], &ctx, parse)
+}
+
+/// Parses cargo's `--message-format=json` stream, shared by the clippy,
+/// check and test adapters. Each `compiler-message` line becomes one
+/// diagnostic at its primary span; duplicates from multiple targets are
+/// dropped. Returns `None` when no cargo JSON line is present.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut seen_cargo_json = false;
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for line in output.lines() {
+        let Ok(json) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let Some(reason) = json.get("reason").and_then(|r| r.as_str()) else {
+            continue;
+        };
+        seen_cargo_json = true;
+        if reason != "compiler-message" {
+            continue;
+        }
+        if let Some(d) = json.get("message").and_then(parse_message)
+            && !diagnostics.contains(&d)
+        {
+            diagnostics.push(d);
+        }
+    }
+    seen_cargo_json.then_some(diagnostics)
+}
+
+fn parse_message(message: &Value) -> Option<Diagnostic> {
+    let text = diagnostic::json_string(message, "message")?;
+    let level = message.get("level").and_then(|l| l.as_str())?;
+    let severity = match level {
+        "error" | "error: internal compiler error" => Severity::Error,
+        "warning" => Severity::Warning,
+        "note" | "help" => Severity::Info,
+        _ => return None,
+    };
+    let rule = message
+        .get("code")
+        .and_then(|c| diagnostic::json_string(c, "code"));
+    let spans = message.get("spans").and_then(|s| s.as_array());
+    let primary = spans.and_then(|spans| {
+        spans
+            .iter()
+            .find(|s| s.get("is_primary").and_then(|p| p.as_bool()) == Some(true))
+            .or_else(|| spans.first())
+    });
+    if primary.is_none() && rule.is_none() && SUMMARY_RE.is_match(&text) {
+        return None;
+    }
+
+    Some(Diagnostic {
+        rule,
+        severity,
+        file: primary
+            .and_then(|s| diagnostic::json_string(s, "file_name"))
+            .map(Into::into),
+        line: primary.and_then(|s| diagnostic::json_u32(s, "line_start")),
+        column: primary.and_then(|s| diagnostic::json_u32(s, "column_start")),
+        end_line: primary.and_then(|s| diagnostic::json_u32(s, "line_end")),
+        end_column: primary.and_then(|s| diagnostic::json_u32(s, "column_end")),
+        message: text,
+        fix: help(message),
+        ..Default::default()
+    })
+}
+
+/// The first `help` child, with its suggested replacement when present.
+fn help(message: &Value) -> Option<String> {
+    let child = message
+        .get("children")?
+        .as_array()?
+        .iter()
+        .find(|c| c.get("level").and_then(|l| l.as_str()) == Some("help"))?;
+    let text = diagnostic::json_string(child, "message")?;
+    let replacement = child
+        .get("spans")
+        .and_then(|s| s.as_array())
+        .and_then(|spans| {
+            spans
+                .iter()
+                .find_map(|s| diagnostic::json_string(s, "suggested_replacement"))
+        });
+    Some(match replacement {
+        Some(r) if !r.is_empty() => format!("{text}: `{r}`"),
+        _ => text,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const UNUSED: &str = r#"{"reason":"compiler-message","package_id":"demo 0.1.0","message":{"message":"unused variable: `x`","code":{"code":"unused_variables","explanation":null},"level":"warning","spans":[{"file_name":"src/main.rs","line_start":2,"line_end":2,"column_start":9,"column_end":10,"is_primary":true,"suggested_replacement":null}],"children":[{"message":"if this is intentional, prefix it with an underscore","level":"help","spans":[{"file_name":"src/main.rs","line_start":2,"line_end":2,"column_start":9,"column_end":10,"is_primary":true,"suggested_replacement":"_x"}],"children":[]}]}}"#;
+
+    #[test]
+    fn parses_compiler_messages() {
+        let output = format!(
+            "{UNUSED}\n{}\n{}\n{}\n",
+            r#"{"reason":"compiler-message","message":{"message":"mismatched types","code":{"code":"E0308"},"level":"error","spans":[{"file_name":"src/lib.rs","line_start":5,"line_end":5,"column_start":3,"column_end":8,"is_primary":false},{"file_name":"src/lib.rs","line_start":7,"line_end":7,"column_start":12,"column_end":15,"is_primary":true}],"children":[]}}"#,
+            r#"{"reason":"compiler-message","message":{"message":"aborting due to 1 previous error","code":null,"level":"error","spans":[],"children":[]}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+        );
+        let diags = parse(&output).unwrap();
+        assert_eq!(diags.len(), 2);
+
+        assert_eq!(diags[0].rule.as_deref(), Some("unused_variables"));
+        assert_eq!(diags[0].severity, Severity::Warning);
+        assert_eq!(diags[0].line, Some(2));
+        assert_eq!(diags[0].column, Some(9));
+        assert_eq!(
+            diags[0].fix.as_deref(),
+            Some("if this is intentional, prefix it with an underscore: `_x`")
+        );
+
+        assert_eq!(diags[1].rule.as_deref(), Some("E0308"));
+        assert_eq!(diags[1].severity, Severity::Error);
+        assert_eq!(diags[1].file.as_deref(), Some(Path::new("src/lib.rs")));
+        assert_eq!(diags[1].line, Some(7));
+    }
+
+    #[test]
+    fn drops_duplicates_across_targets() {
+        let output = format!("{UNUSED}\n{UNUSED}\n");
+        assert_eq!(parse(&output).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn clean_build_is_empty_and_text_is_none() {
+        let clean = r#"{"reason":"build-finished","success":true}"#;
+        assert_eq!(parse(clean), Some(vec![]));
+        assert!(parse("error: could not find `Cargo.toml`").is_none());
+    }
+
+    #[test]
+    fn skips_without_cargo_toml() {
+        let result = run(&ToolContext::new(ProjectInfo::default()));
+        assert_eq!(result.name, "cargo-check");
+        assert!(result.output.is_empty());
+    }
+}
@@ -0,0 +1,32 @@
+use super::{ToolContext, ToolResult, cargo_check};
+use crate::project::ProjectInfo;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_cargo_toml
+}
+
+/// Builds the test targets without running them, catching errors in
+/// `#[cfg(test)]` code that `cargo check` skips.
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("cargo-test");
+    }
+
+    cargo_check::run_cargo(
+        "cargo-test",
+        &["test", "--no-run", "--message-format=json"],
+        ctx,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_without_cargo_toml() {
+        let result = run(&ToolContext::new(ProjectInfo::default()));
+        assert_eq!(result.name, "cargo-test");
+        assert!(!result.success);
+    }
+}
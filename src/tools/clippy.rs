@@ -0,0 +1,26 @@
+use super::{ToolContext, ToolResult, cargo_check};
+use crate::project::ProjectInfo;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_cargo_toml
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("clippy");
+    }
+
+    cargo_check::run_cargo("clippy", &["clippy", "--message-format=json"], ctx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn skips_without_cargo_toml() {
+        let result = run(&ToolContext::new(ProjectInfo::default()));
+        assert_eq!(result.name, "clippy");
+        assert!(!result.success);
+    }
+}
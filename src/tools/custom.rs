@@ -0,0 +1,151 @@
+use super::{ToolContext, ToolResult};
+use crate::config::CustomTool;
+use crate::project::ProjectInfo;
+use crate::resolve;
+use std::path::PathBuf;
+use std::process::Command;
+
+/// A custom tool runs when every non-empty condition list has a match: one of
+/// `when.files` exists under the root and one of `when.dependencies` is
+/// declared in `package.json`.
+pub fn applies(tool: &CustomTool, project: &ProjectInfo) -> bool {
+    let when = &tool.when;
+    (when.files.is_empty() || when.files.iter().any(|f| project.root.join(f).exists()))
+        && (when.dependencies.is_empty()
+            || when
+                .dependencies
+                .iter()
+                .any(|d| project.dependencies.contains(d)))
+}
+
+/// Bare command names are resolved like the built-in tools; paths are taken
+/// relative to the project root.
+pub fn bin(tool: &CustomTool, project: &ProjectInfo) -> PathBuf {
+    if tool.command.contains('/') {
+        project.root.join(&tool.command)
+    } else {
+        resolve::resolve_bin(&tool.command, &project.root)
+    }
+}
+
+pub fn run(tool: &CustomTool, ctx: &ToolContext) -> ToolResult {
+    let project = &ctx.project;
+    if !applies(tool, project) {
+        return ToolResult::skipped(tool.name.clone());
+    }
+
+    let parse = match tool.parser.as_str() {
+        "raw" => None,
+        name => match super::parser(name) {
+            Some(parse) => Some(parse),
+            None => {
+                eprintln!(
+                    "reviews: warning: {}: unknown parser {:?}, showing raw output",
+                    tool.name, name
+                );
+                None
+            }
+        },
+    };
+
+    let subdir = tool.cwd.clone().unwrap_or_default();
+    let mut cmd = Command::new(bin(tool, project));
+    cmd.args(&tool.args)
+        .envs(&tool.env)
+        .current_dir(project.root.join(&subdir));
+    let timeout = tool.timeout.unwrap_or(super::TOOL_TIMEOUT);
+    let mut result = super::run_with_timeout_duration(tool.name.clone(), cmd, timeout, parse);
+    for d in result.diagnostics.iter_mut().flatten() {
+        d.relativize(&project.root);
+    }
+    result.rebase(&subdir);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Conditions;
+    use crate::test_utils::TempDir;
+    use std::fs;
+
+    fn tool(command: &str, args: &[&str]) -> CustomTool {
+        CustomTool {
+            name: "custom".into(),
+            command: command.into(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            cwd: None,
+            when: Conditions::default(),
+            parser: "raw".into(),
+            timeout: None,
+            env: Default::default(),
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn applies_checks_files_and_dependencies() {
+        let tmp = TempDir::new("custom-applies");
+        fs::write(tmp.join("eslint.config.js"), "").unwrap();
+        let mut project = ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        };
+        let mut t = tool("eslint", &[]);
+        assert!(applies(&t, &project));
+
+        t.when.files = vec!["missing.json".into(), "eslint.config.js".into()];
+        assert!(applies(&t, &project));
+
+        t.when.dependencies = vec!["eslint".into()];
+        assert!(!applies(&t, &project));
+        project.dependencies.insert("eslint".into());
+        assert!(applies(&t, &project));
+    }
+
+    #[test]
+    fn runs_raw_command_in_subdirectory() {
+        let tmp = TempDir::new("custom-raw");
+        fs::create_dir_all(tmp.join("packages/web")).unwrap();
+        let mut t = tool("sh", &["-c", "pwd; echo $CUSTOM_VAR"]);
+        t.cwd = Some("packages/web".into());
+        t.env.insert("CUSTOM_VAR".into(), "hello".into());
+        let ctx = ToolContext::new(ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        });
+
+        let result = run(&t, &ctx);
+        assert_eq!(result.name, "custom");
+        assert!(result.success);
+        assert!(result.diagnostics.is_none());
+        assert!(
+            result.output.ends_with("packages/web\nhello"),
+            "{}",
+            result.output
+        );
+    }
+
+    #[test]
+    fn parsed_paths_are_rebased_onto_cwd() {
+        let tmp = TempDir::new("custom-parsed");
+        fs::create_dir_all(tmp.join("web")).unwrap();
+        let mut t = tool(
+            "echo",
+            &["src/a.ts(3,7): error TS2322: Type 'string' is not assignable."],
+        );
+        t.cwd = Some("web".into());
+        t.parser = "tsgo".into();
+        let ctx = ToolContext::new(ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        });
+
+        let diagnostics = run(&t, &ctx).diagnostics.unwrap();
+        assert_eq!(diagnostics[0].tool, "custom");
+        assert_eq!(
+            diagnostics[0].file.as_deref(),
+            Some(std::path::Path::new("web/src/a.ts"))
+        );
+    }
+}
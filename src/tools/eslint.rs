@@ -0,0 +1,204 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_eslint_config
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("eslint");
+    }
+    let files = match ctx.js_files() {
+        Some(files) if files.is_empty() => return ToolResult::skipped("eslint"),
+        Some(files) => files,
+        None => vec![PathBuf::from(".")],
+    };
+
+    let bin = resolve::resolve_bin("eslint", &ctx.project.root);
+    super::run_command("eslint", &bin, &["--format", "json"], &files, ctx, parse)
+}
+
+/// Parses `eslint --format json`: one entry per file, each with its
+/// `messages`. Severity 2 is an error, 1 a warning.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let mut diagnostics = Vec::new();
+    for file in json.as_array()? {
+        let path = diagnostic::json_string(file, "filePath");
+        let messages = file.get("messages").and_then(|m| m.as_array());
+        for message in messages.into_iter().flatten() {
+            if let Some(d) = parse_message(message, path.as_deref()) {
+                diagnostics.push(d);
+            }
+        }
+    }
+    Some(diagnostics)
+}
+
+fn parse_message(message: &Value, path: Option<&str>) -> Option<Diagnostic> {
+    let fatal = message.get("fatal").and_then(|f| f.as_bool()) == Some(true);
+    let severity = match message.get("severity").and_then(|s| s.as_u64()) {
+        _ if fatal => Severity::Error,
+        Some(2) => Severity::Error,
+        Some(1) => Severity::Warning,
+        _ => Severity::Info,
+    };
+    let suggestion = message
+        .get("suggestions")
+        .and_then(|s| s.as_array())
+        .and_then(|s| s.first())
+        .and_then(|s| diagnostic::json_string(s, "desc"));
+    let fix = suggestion.or_else(|| {
+        message
+            .get("fix")
+            .map(|_| "auto-fixable with `eslint --fix`".to_string())
+    });
+    Some(Diagnostic {
+        rule: diagnostic::json_string(message, "ruleId"),
+        severity,
+        file: path.map(Into::into),
+        line: diagnostic::json_u32(message, "line"),
+        column: diagnostic::json_u32(message, "column"),
+        end_line: diagnostic::json_u32(message, "endLine"),
+        end_column: diagnostic::json_u32(message, "endColumn"),
+        message: diagnostic::json_string(message, "message")?,
+        fix,
+        ..Default::default()
+    })
+}
+
+/// The bare rule name shared by both tools: `eslint(no-debugger)` and
+/// `no-debugger`, or `eslint-plugin-react(jsx-key)` and `react/jsx-key`.
+fn rule_name(rule: &str) -> &str {
+    if let Some(inner) = rule
+        .strip_suffix(')')
+        .and_then(|r| r.split_once('(').map(|(_, name)| name))
+    {
+        return inner;
+    }
+    rule.rsplit('/').next().unwrap_or(rule)
+}
+
+/// Removes ESLint findings that oxlint reported at the same file and line
+/// under the same rule. Returns how many were dropped.
+pub fn drop_oxlint_duplicates(results: &mut [ToolResult]) -> usize {
+    let key = |d: &Diagnostic| {
+        let rule = d.rule.as_deref().map(rule_name)?;
+        Some((d.file.clone()?, d.line?, rule.to_string()))
+    };
+    let seen: HashSet<_> = results
+        .iter()
+        .filter(|r| r.name == "oxlint")
+        .flat_map(|r| r.diagnostics.iter().flatten())
+        .filter_map(key)
+        .collect();
+    if seen.is_empty() {
+        return 0;
+    }
+
+    let mut dropped = 0;
+    for result in results.iter_mut().filter(|r| r.name == "eslint") {
+        if let Some(diagnostics) = &mut result.diagnostics {
+            let before = diagnostics.len();
+            diagnostics.retain(|d| key(d).is_none_or(|k| !seen.contains(&k)));
+            dropped += before - diagnostics.len();
+        }
+    }
+    dropped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    const OUTPUT: &str = r#"[
+        {"filePath": "/repo/src/a.js", "messages": [
+            {"ruleId": "no-unused-vars", "severity": 2, "message": "'x' is assigned a value but never used.",
+             "line": 1, "column": 7, "endLine": 1, "endColumn": 8,
+             "suggestions": [{"desc": "Remove unused variable 'x'."}]},
+            {"ruleId": "semi", "severity": 1, "message": "Missing semicolon.", "line": 2, "column": 10,
+             "fix": {"range": [20, 20], "text": ";"}}
+        ], "errorCount": 1, "warningCount": 1},
+        {"filePath": "/repo/src/b.js", "messages": [
+            {"ruleId": null, "fatal": true, "severity": 2, "message": "Parsing error: Unexpected token", "line": 3, "column": 1}
+        ]},
+        {"filePath": "/repo/src/c.js", "messages": []}
+    ]"#;
+
+    #[test]
+    fn parses_eslint_json() {
+        let diags = parse(OUTPUT).unwrap();
+        assert_eq!(diags.len(), 3);
+        assert_eq!(diags[0].rule.as_deref(), Some("no-unused-vars"));
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("/repo/src/a.js")));
+        assert_eq!(diags[0].fix.as_deref(), Some("Remove unused variable 'x'."));
+        assert_eq!(diags[1].severity, Severity::Warning);
+        assert_eq!(
+            diags[1].fix.as_deref(),
+            Some("auto-fixable with `eslint --fix`")
+        );
+        assert_eq!(diags[2].rule, None);
+        assert_eq!(diags[2].severity, Severity::Error);
+    }
+
+    #[test]
+    fn rejects_non_json() {
+        assert!(parse("Oops! Something went wrong!").is_none());
+    }
+
+    #[test]
+    fn normalizes_rule_names() {
+        assert_eq!(rule_name("eslint(no-debugger)"), "no-debugger");
+        assert_eq!(rule_name("eslint-plugin-react(jsx-key)"), "jsx-key");
+        assert_eq!(rule_name("react/jsx-key"), "jsx-key");
+        assert_eq!(
+            rule_name("@typescript-eslint/no-explicit-any"),
+            "no-explicit-any"
+        );
+        assert_eq!(rule_name("semi"), "semi");
+    }
+
+    #[test]
+    fn drops_findings_oxlint_also_reports() {
+        let finding = |tool: &'static str, rule: &str, line| Diagnostic {
+            tool: tool.into(),
+            rule: Some(rule.into()),
+            file: Some("src/a.js".into()),
+            line: Some(line),
+            message: "m".into(),
+            ..Default::default()
+        };
+        let result = |name: &'static str, diagnostics| ToolResult {
+            name: name.into(),
+            output: String::new(),
+            success: false,
+            diagnostics: Some(diagnostics),
+        };
+        let mut results = vec![
+            result(
+                "oxlint",
+                vec![finding("oxlint", "eslint(no-unused-vars)", 1)],
+            ),
+            result(
+                "eslint",
+                vec![
+                    finding("eslint", "no-unused-vars", 1),
+                    finding("eslint", "no-unused-vars", 5),
+                    finding("eslint", "custom/rule", 1),
+                ],
+            ),
+        ];
+        assert_eq!(drop_oxlint_duplicates(&mut results), 1);
+        let kept = results[1].diagnostics.as_ref().unwrap();
+        assert_eq!(kept.len(), 2);
+        assert_eq!(results[0].diagnostics.as_ref().unwrap().len(), 1);
+    }
+}
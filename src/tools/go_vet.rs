@@ -0,0 +1,74 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `./pkg/a.go:10:2: message`, optionally prefixed with `vet: `; the column
+/// is omitted for some analyzers.
+static LOCATED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?:vet: )?(?:\./)?(?P<file>[^:\s][^:]*\.go):(?P<line>\d+):(?:(?P<col>\d+):)? (?P<msg>.+)$")
+        .unwrap()
+});
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_go_mod
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("go-vet");
+    }
+
+    let bin = resolve::resolve_bin("go", &ctx.project.root);
+    super::run_command("go-vet", &bin, &["vet", "./..."], &[], ctx, parse)
+}
+
+/// Parses `go vet` text output. `# package` headers are skipped; a clean run
+/// prints nothing.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let mut unrecognized = false;
+    for line in output.lines().filter(|l| !l.trim().is_empty()) {
+        if let Some(caps) = LOCATED_RE.captures(line) {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Warning,
+                file: Some(caps["file"].into()),
+                line: caps["line"].parse().ok(),
+                column: caps.name("col").and_then(|m| m.as_str().parse().ok()),
+                message: caps["msg"].to_string(),
+                ..Default::default()
+            });
+        } else if !line.starts_with('#') && !line.starts_with(char::is_whitespace) {
+            unrecognized = true;
+        }
+    }
+    (!unrecognized || !diagnostics.is_empty()).then_some(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_vet_output() {
+        let output = "# example.com/demo/pkg\n\
+./pkg/fmt.go:10:2: fmt.Printf format %d has arg s of wrong type string\n\
+vet: internal/db.go:4:1: unreachable code\n\
+main.go:7: self-assignment of x to x\n";
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 3);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("pkg/fmt.go")));
+        assert_eq!((diags[0].line, diags[0].column), (Some(10), Some(2)));
+        assert_eq!(diags[1].file.as_deref(), Some(Path::new("internal/db.go")));
+        assert_eq!(diags[2].column, None);
+    }
+
+    #[test]
+    fn clean_and_unrecognized_output() {
+        assert_eq!(parse(""), Some(vec![]));
+        assert!(parse("go: cannot find main module").is_none());
+    }
+}
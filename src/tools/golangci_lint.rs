@@ -0,0 +1,109 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+
+const CONFIG_FILES: &[&str] = &[
+    ".golangci.yml",
+    ".golangci.yaml",
+    ".golangci.toml",
+    ".golangci.json",
+];
+
+/// Runs only where the project opted in with a golangci-lint config, since
+/// its default linter set overlaps with go vet and staticcheck.
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_go_mod && CONFIG_FILES.iter().any(|f| project.root.join(f).is_file())
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("golangci-lint");
+    }
+
+    let bin = resolve::resolve_bin("golangci-lint", &ctx.project.root);
+    super::run_command(
+        "golangci-lint",
+        &bin,
+        &["run", "--out-format", "json"],
+        &[],
+        ctx,
+        parse,
+    )
+}
+
+/// Parses `golangci-lint run --out-format json`. `Issues` is `null` on a
+/// clean run; the linter name becomes the rule.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    // Warnings may follow the JSON document on the same stream.
+    let json_line = output.lines().find(|l| l.starts_with('{'))?;
+    let json: Value = serde_json::from_str(json_line).ok()?;
+    let issues = json.get("Issues")?;
+    let Some(issues) = issues.as_array() else {
+        return issues.is_null().then(Vec::new);
+    };
+    Some(issues.iter().filter_map(parse_issue).collect())
+}
+
+fn parse_issue(issue: &Value) -> Option<Diagnostic> {
+    let pos = issue.get("Pos");
+    Some(Diagnostic {
+        rule: diagnostic::json_string(issue, "FromLinter"),
+        severity: issue
+            .get("Severity")
+            .and_then(|s| s.as_str())
+            .filter(|s| !s.is_empty())
+            .map_or(Severity::Warning, Severity::from_label),
+        file: pos
+            .and_then(|p| diagnostic::json_string(p, "Filename"))
+            .map(Into::into),
+        line: pos.and_then(|p| diagnostic::json_u32(p, "Line")),
+        column: pos
+            .and_then(|p| diagnostic::json_u32(p, "Column"))
+            .filter(|c| *c > 0),
+        message: diagnostic::json_string(issue, "Text")?,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::fs;
+    use std::path::Path;
+
+    #[test]
+    fn applies_only_with_config() {
+        let tmp = TempDir::new("golangci-applies");
+        let mut project = ProjectInfo {
+            root: tmp.to_path_buf(),
+            has_go_mod: true,
+            ..Default::default()
+        };
+        assert!(!applies(&project));
+        fs::write(tmp.join(".golangci.yml"), "linters: {}\n").unwrap();
+        assert!(applies(&project));
+        project.has_go_mod = false;
+        assert!(!applies(&project));
+    }
+
+    #[test]
+    fn parses_issues() {
+        let output = r#"{"Issues":[{"FromLinter":"errcheck","Text":"Error return value of `f.Close` is not checked","Severity":"","SourceLines":["\tf.Close()"],"Pos":{"Filename":"internal/io.go","Offset":120,"Line":14,"Column":9}}],"Report":{"Linters":[]}}
+level=warning msg="[runner] deprecated option""#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].rule.as_deref(), Some("errcheck"));
+        assert_eq!(diags[0].severity, Severity::Warning);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("internal/io.go")));
+        assert_eq!((diags[0].line, diags[0].column), (Some(14), Some(9)));
+    }
+
+    #[test]
+    fn null_issues_and_text() {
+        assert_eq!(parse(r#"{"Issues":null,"Report":{}}"#), Some(vec![]));
+        assert!(parse("Error: can't load config").is_none());
+    }
+}
@@ -1,36 +1,184 @@
-use super::ToolResult;
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic};
 use crate::project::ProjectInfo;
 use crate::resolve;
+use serde_json::Value;
 
-pub fn run(project: &ProjectInfo) -> ToolResult {
-    if !project.has_package_json {
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_package_json
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    let project = &ctx.project;
+    if !applies(project) {
         return ToolResult::skipped("knip");
     }
 
     let bin = resolve::resolve_bin("knip", &project.root);
-    super::run_js_command(
+    super::run_command(
         "knip",
         &bin,
         &["--reporter", "json", "--no-exit-code"],
-        project,
+        &[],
+        ctx,
+        parse,
     )
 }
 
+fn describe(issue_type: &str) -> &str {
+    match issue_type {
+        "files" => "Unused file",
+        "dependencies" => "Unused dependency",
+        "devDependencies" => "Unused devDependency",
+        "optionalPeerDependencies" => "Referenced optional peerDependency",
+        "unlisted" => "Unlisted dependency",
+        "binaries" => "Unlisted binary",
+        "unresolved" => "Unresolved import",
+        "exports" => "Unused export",
+        "nsExports" => "Unused export in namespace",
+        "types" => "Unused exported type",
+        "nsTypes" => "Unused exported type in namespace",
+        "enumMembers" => "Unused enum member",
+        "classMembers" => "Unused class member",
+        "duplicates" => "Duplicate export",
+        other => other,
+    }
+}
+
+/// Parses `knip --reporter json`: a top-level list of unused files plus one
+/// entry per file with arrays (or parent-keyed maps) of issues by type.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output).ok()?;
+    let issues = json.get("issues")?.as_array()?;
+    let mut diagnostics = Vec::new();
+
+    if let Some(files) = json.get("files").and_then(|f| f.as_array()) {
+        for file in files.iter().filter_map(|f| f.as_str()) {
+            diagnostics.push(Diagnostic {
+                rule: Some("files".into()),
+                file: Some(file.into()),
+                message: describe("files").into(),
+                ..Default::default()
+            });
+        }
+    }
+
+    for issue in issues {
+        let Some(file) = issue.get("file").and_then(|f| f.as_str()) else {
+            continue;
+        };
+        let Some(fields) = issue.as_object() else {
+            continue;
+        };
+        for (issue_type, value) in fields {
+            if issue_type == "file" || issue_type == "owners" {
+                continue;
+            }
+            match value {
+                Value::Array(items) => {
+                    for item in items {
+                        diagnostics.push(item_diagnostic(file, issue_type, None, item));
+                    }
+                }
+                Value::Object(groups) => {
+                    for (parent, items) in groups {
+                        for item in items.as_array().into_iter().flatten() {
+                            diagnostics.push(item_diagnostic(file, issue_type, Some(parent), item));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Some(diagnostics)
+}
+
+fn item_diagnostic(file: &str, issue_type: &str, parent: Option<&str>, item: &Value) -> Diagnostic {
+    // `duplicates` items are arrays of the symbols that share one export.
+    let (name, position) = match item {
+        Value::Array(dups) => {
+            let names: Vec<_> = dups
+                .iter()
+                .filter_map(|d| d.get("name").and_then(|n| n.as_str()))
+                .collect();
+            (names.join(", "), dups.first().unwrap_or(item))
+        }
+        other => (
+            other
+                .get("name")
+                .and_then(|n| n.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            other,
+        ),
+    };
+    let name = match parent {
+        Some(parent) => format!("{parent}.{name}"),
+        None => name,
+    };
+    Diagnostic {
+        rule: Some(issue_type.into()),
+        file: Some(file.into()),
+        line: diagnostic::json_u32(position, "line"),
+        column: diagnostic::json_u32(position, "col"),
+        message: format!("{} `{}`", describe(issue_type), name),
+        ..Default::default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn skips_without_package_json() {
         let info = ProjectInfo {
             root: PathBuf::from("/tmp/nonexistent"),
-            has_package_json: false,
-            has_tsconfig: false,
-            has_react: false,
+            ..Default::default()
         };
-        let result = run(&info);
+        let result = run(&ToolContext::new(info));
         assert!(!result.success);
         assert!(result.output.is_empty());
     }
+
+    #[test]
+    fn parses_files_and_issues() {
+        let output = r#"{
+            "files": ["src/unused.ts"],
+            "issues": [{
+                "file": "src/lib.ts",
+                "owners": [],
+                "dependencies": [],
+                "exports": [{"name": "helper", "line": 4, "col": 17, "pos": 60}],
+                "enumMembers": {"Color": [{"name": "Red", "line": 9, "col": 3}]},
+                "duplicates": [[{"name": "a", "line": 1, "col": 1}, {"name": "b"}]]
+            }]
+        }"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 4);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("src/unused.ts")));
+        assert_eq!(diags[0].message, "Unused file");
+
+        let export = diags
+            .iter()
+            .find(|d| d.rule.as_deref() == Some("exports"))
+            .unwrap();
+        assert_eq!(export.message, "Unused export `helper`");
+        assert_eq!((export.line, export.column), (Some(4), Some(17)));
+
+        assert!(
+            diags
+                .iter()
+                .any(|d| d.message == "Unused enum member `Color.Red`")
+        );
+        assert!(diags.iter().any(|d| d.message == "Duplicate export `a, b`"));
+    }
+
+    #[test]
+    fn rejects_non_json() {
+        assert!(parse("Unused files (1)\nsrc/unused.ts").is_none());
+    }
 }
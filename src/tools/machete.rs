@@ -0,0 +1,95 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_cargo_toml
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("cargo-machete");
+    }
+
+    let bin = resolve::resolve_bin("cargo-machete", &ctx.project.root);
+    super::run_command("cargo-machete", &bin, &[], &[], ctx, parse)
+}
+
+/// Parses cargo-machete's report:
+///
+/// ```text
+/// cargo-machete found the following unused dependencies in this directory:
+/// demo -- ./Cargo.toml:
+///         serde
+/// ```
+///
+/// Returns `None` when neither a finding nor the all-clear line is present.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let mut manifest: Option<(String, String)> = None;
+    let mut recognized = false;
+    for line in output.lines() {
+        if line.contains("didn't find any unused dependencies") {
+            recognized = true;
+        } else if let Some((krate, path)) = line.split_once(" -- ")
+            && let Some(path) = path.trim_end().strip_suffix(':')
+        {
+            recognized = true;
+            let path = path.strip_prefix("./").unwrap_or(path);
+            manifest = Some((krate.trim().to_string(), path.to_string()));
+        } else if line.starts_with(['\t', ' '])
+            && let Some((krate, path)) = &manifest
+            && !line.trim().is_empty()
+        {
+            let dependency = line.trim();
+            diagnostics.push(Diagnostic {
+                rule: Some("unused-dependency".into()),
+                severity: Severity::Warning,
+                file: Some(path.into()),
+                message: format!("Unused dependency `{dependency}` in `{krate}`"),
+                fix: Some(format!("remove `{dependency}` from {path}")),
+                ..Default::default()
+            });
+        } else {
+            manifest = None;
+        }
+    }
+    recognized.then_some(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_unused_dependencies() {
+        let output = "Analyzing dependencies of crates in this directory...\n\
+cargo-machete found the following unused dependencies in this directory:\n\
+demo -- ./Cargo.toml:\n\
+\tserde\n\
+\tregex\n\
+tools -- ./crates/tools/Cargo.toml:\n\
+\tanyhow\n\
+\n\
+If you believe cargo-machete has detected an unused dependency incorrectly,\n\
+you can add the dependency to the list of dependencies to ignore\n";
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 3);
+        assert_eq!(diags[0].message, "Unused dependency `serde` in `demo`");
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("Cargo.toml")));
+        assert_eq!(
+            diags[2].file.as_deref(),
+            Some(Path::new("crates/tools/Cargo.toml"))
+        );
+    }
+
+    #[test]
+    fn clean_and_unrecognized_output() {
+        let clean = "Analyzing dependencies of crates in this directory...\n\
+cargo-machete didn't find any unused dependencies in this directory. Good job!\nDone!\n";
+        assert_eq!(parse(clean), Some(vec![]));
+        assert!(parse("error: no such command").is_none());
+    }
+}
@@ -1,39 +1,460 @@
+pub mod astro;
+pub mod audit;
+pub mod biome;
+pub mod cargo_check;
+pub mod cargo_test;
+pub mod clippy;
+pub mod custom;
+pub mod eslint;
+pub mod go_vet;
+pub mod golangci_lint;
 pub mod knip;
+pub mod machete;
+pub mod mypy;
 pub mod oxlint;
+pub mod pyright;
 pub mod react_doctor;
+pub mod ruff;
+pub mod staticcheck;
+pub mod svelte_check;
+pub mod test_runner;
 pub mod tsgo;
+pub mod vue_tsc;
 
+use crate::config::{ToolSettings, ToolsConfig};
+use crate::diagnostic::{Diagnostic, Parser, Severity};
+use crate::project::ProjectInfo;
 use crate::sanitize;
+use std::borrow::Cow;
 use std::os::unix::process::CommandExt;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::{Command, Output, Stdio};
 use std::sync::mpsc;
 use std::time::Duration;
 
 const TOOL_TIMEOUT: Duration = Duration::from_secs(60);
-const MAX_OUTPUT_SIZE: usize = 102_400;
+const VERSION_TIMEOUT: Duration = Duration::from_secs(5);
+pub(crate) const MAX_OUTPUT_SIZE: usize = 102_400;
 /// Total budget for combined additionalContext across all tools
 const MAX_TOTAL_OUTPUT: usize = 204_800;
+/// Above this many changed files, file-capable tools run project-wide
+/// instead, keeping the command line well under ARG_MAX.
+const MAX_FILE_ARGS: usize = 500;
+const JS_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "js", "jsx", "mjs", "cjs"];
+const PY_EXTENSIONS: &[&str] = &["py", "pyi"];
 
-// TS-001: Using &'static str because all tool names are compile-time constants.
-// If dynamic tool registration is needed, change to Cow<'static, str>.
+/// A built-in tool adapter and how to decide whether it runs.
+pub struct Tool {
+    pub name: &'static str,
+    /// Key under `tools` in the config (e.g. `react_doctor`).
+    pub key: &'static str,
+    /// Executable looked up by `resolve::resolve_bin`.
+    pub bin: &'static str,
+    pub settings: fn(&ToolsConfig) -> &ToolSettings,
+    /// Whether the project has what the tool needs (e.g. a `tsconfig.json`).
+    pub applies: fn(&ProjectInfo) -> bool,
+    /// Whole-program tools that must run inside each workspace package
+    /// rather than once at the monorepo root.
+    pub per_package: bool,
+    /// Accepts individual files, so it can lint just the file an agent edit
+    /// touched (see `post_tool_use`).
+    pub files: bool,
+    pub run: fn(&ToolContext) -> ToolResult,
+}
+
+pub const TOOLS: &[Tool] = &[
+    Tool {
+        name: "knip",
+        key: "knip",
+        bin: "knip",
+        settings: |c| &c.knip,
+        applies: knip::applies,
+        per_package: false,
+        files: false,
+        run: knip::run,
+    },
+    Tool {
+        name: "oxlint",
+        key: "oxlint",
+        bin: "oxlint",
+        settings: |c| &c.oxlint,
+        applies: oxlint::applies,
+        per_package: false,
+        files: true,
+        run: oxlint::run,
+    },
+    Tool {
+        name: "tsgo",
+        key: "tsgo",
+        bin: "tsgo",
+        settings: |c| &c.tsgo,
+        applies: tsgo::applies,
+        per_package: true,
+        files: false,
+        run: tsgo::run,
+    },
+    Tool {
+        name: "react-doctor",
+        key: "react_doctor",
+        bin: "react-doctor",
+        settings: |c| &c.react_doctor,
+        applies: react_doctor::applies,
+        per_package: true,
+        files: false,
+        run: react_doctor::run,
+    },
+    Tool {
+        name: "vue-tsc",
+        key: "vue_tsc",
+        bin: "vue-tsc",
+        settings: |c| &c.vue_tsc,
+        applies: vue_tsc::applies,
+        per_package: true,
+        files: false,
+        run: vue_tsc::run,
+    },
+    Tool {
+        name: "svelte-check",
+        key: "svelte_check",
+        bin: "svelte-check",
+        settings: |c| &c.svelte_check,
+        applies: svelte_check::applies,
+        per_package: true,
+        files: false,
+        run: svelte_check::run,
+    },
+    Tool {
+        name: "astro-check",
+        key: "astro_check",
+        bin: "astro",
+        settings: |c| &c.astro_check,
+        applies: astro::applies,
+        per_package: true,
+        files: false,
+        run: astro::run,
+    },
+    Tool {
+        name: "eslint",
+        key: "eslint",
+        bin: "eslint",
+        settings: |c| &c.eslint,
+        applies: eslint::applies,
+        per_package: false,
+        files: true,
+        run: eslint::run,
+    },
+    Tool {
+        name: "biome",
+        key: "biome",
+        bin: "biome",
+        settings: |c| &c.biome,
+        applies: biome::applies,
+        per_package: false,
+        files: true,
+        run: biome::run,
+    },
+    Tool {
+        name: "clippy",
+        key: "clippy",
+        bin: "cargo-clippy",
+        settings: |c| &c.clippy,
+        applies: clippy::applies,
+        per_package: false,
+        files: false,
+        run: clippy::run,
+    },
+    Tool {
+        name: "cargo-check",
+        key: "cargo_check",
+        bin: "cargo",
+        settings: |c| &c.cargo_check,
+        applies: cargo_check::applies,
+        per_package: false,
+        files: false,
+        run: cargo_check::run,
+    },
+    Tool {
+        name: "cargo-test",
+        key: "cargo_test",
+        bin: "cargo",
+        settings: |c| &c.cargo_test,
+        applies: cargo_test::applies,
+        per_package: false,
+        files: false,
+        run: cargo_test::run,
+    },
+    Tool {
+        name: "cargo-machete",
+        key: "machete",
+        bin: "cargo-machete",
+        settings: |c| &c.machete,
+        applies: machete::applies,
+        per_package: false,
+        files: false,
+        run: machete::run,
+    },
+    Tool {
+        name: "cargo-audit",
+        key: "audit",
+        bin: "cargo-audit",
+        settings: |c| &c.audit,
+        applies: audit::applies_cargo,
+        per_package: false,
+        files: false,
+        run: audit::run_cargo,
+    },
+    Tool {
+        name: "npm-audit",
+        key: "audit",
+        bin: "npm",
+        settings: |c| &c.audit,
+        applies: audit::applies_npm,
+        per_package: false,
+        files: false,
+        run: audit::run_npm,
+    },
+    Tool {
+        name: "pnpm-audit",
+        key: "audit",
+        bin: "pnpm",
+        settings: |c| &c.audit,
+        applies: audit::applies_pnpm,
+        per_package: false,
+        files: false,
+        run: audit::run_pnpm,
+    },
+    Tool {
+        name: "yarn-audit",
+        key: "audit",
+        bin: "yarn",
+        settings: |c| &c.audit,
+        applies: audit::applies_yarn,
+        per_package: false,
+        files: false,
+        run: audit::run_yarn,
+    },
+    Tool {
+        name: "ruff",
+        key: "ruff",
+        bin: "ruff",
+        settings: |c| &c.ruff,
+        applies: ruff::applies,
+        per_package: false,
+        files: true,
+        run: ruff::run,
+    },
+    Tool {
+        name: "mypy",
+        key: "mypy",
+        bin: "mypy",
+        settings: |c| &c.mypy,
+        applies: mypy::applies,
+        per_package: false,
+        files: false,
+        run: mypy::run,
+    },
+    Tool {
+        name: "pyright",
+        key: "pyright",
+        bin: "pyright",
+        settings: |c| &c.pyright,
+        applies: pyright::applies,
+        per_package: false,
+        files: false,
+        run: pyright::run,
+    },
+    Tool {
+        name: "go-vet",
+        key: "go_vet",
+        bin: "go",
+        settings: |c| &c.go_vet,
+        applies: go_vet::applies,
+        per_package: false,
+        files: false,
+        run: go_vet::run,
+    },
+    Tool {
+        name: "staticcheck",
+        key: "staticcheck",
+        bin: "staticcheck",
+        settings: |c| &c.staticcheck,
+        applies: staticcheck::applies,
+        per_package: false,
+        files: false,
+        run: staticcheck::run,
+    },
+    Tool {
+        name: "golangci-lint",
+        key: "golangci_lint",
+        bin: "golangci-lint",
+        settings: |c| &c.golangci_lint,
+        applies: golangci_lint::applies,
+        per_package: false,
+        files: false,
+        run: golangci_lint::run,
+    },
+    Tool {
+        name: "vitest",
+        key: "tests",
+        bin: "vitest",
+        settings: |c| &c.tests,
+        applies: test_runner::applies_vitest,
+        per_package: false,
+        files: false,
+        run: test_runner::run_vitest,
+    },
+    Tool {
+        name: "jest",
+        key: "tests",
+        bin: "jest",
+        settings: |c| &c.tests,
+        applies: test_runner::applies_jest,
+        per_package: false,
+        files: false,
+        run: test_runner::run_jest,
+    },
+];
+
+/// Looks up a built-in structured parser by tool name, for `custom_tools`.
+pub fn parser(name: &str) -> Option<Parser> {
+    match name {
+        "knip" => Some(knip::parse),
+        "oxlint" => Some(oxlint::parse),
+        "tsgo" => Some(tsgo::parse),
+        "react-doctor" => Some(react_doctor::parse),
+        "vue-tsc" => Some(tsgo::parse),
+        "svelte-check" => Some(svelte_check::parse),
+        "astro-check" => Some(astro::parse),
+        "eslint" => Some(eslint::parse),
+        "biome" => Some(biome::parse),
+        "clippy" | "cargo-check" | "cargo-test" => Some(cargo_check::parse),
+        "cargo-machete" => Some(machete::parse),
+        "cargo-audit" => Some(audit::parse_cargo),
+        "npm-audit" => Some(audit::parse_npm),
+        "pnpm-audit" => Some(audit::parse_pnpm),
+        "yarn-audit" => Some(audit::parse_yarn),
+        "ruff" => Some(ruff::parse),
+        "mypy" => Some(mypy::parse),
+        "pyright" => Some(pyright::parse),
+        "go-vet" => Some(go_vet::parse),
+        "staticcheck" => Some(staticcheck::parse),
+        "golangci-lint" => Some(golangci_lint::parse),
+        "vitest" | "jest" => Some(test_runner::parse),
+        _ => None,
+    }
+}
+
+/// Per-invocation input handed to each tool adapter.
+#[derive(Debug, Clone)]
+pub struct ToolContext {
+    pub project: ProjectInfo,
+    /// Changed files relative to `project.root` when a diff scope is active,
+    /// or the paths given to the skill. Tools that accept file arguments lint
+    /// only these; whole-program tools ignore them and are filtered afterwards.
+    pub files: Option<Vec<PathBuf>>,
+    /// The running tool's configured overrides.
+    pub settings: ToolSettings,
+}
+
+impl ToolContext {
+    pub fn new(project: ProjectInfo) -> Self {
+        Self {
+            project,
+            files: None,
+            settings: ToolSettings::default(),
+        }
+    }
+
+    /// Changed files with one of `extensions`, plus any requested directories,
+    /// or `None` when the tool should run project-wide (no diff scope, or too
+    /// many files to pass).
+    pub fn files_with_extensions(&self, extensions: &[&str]) -> Option<Vec<PathBuf>> {
+        let files = self.files.as_ref()?;
+        if files.len() > MAX_FILE_ARGS {
+            return None;
+        }
+        Some(
+            files
+                .iter()
+                .filter(|f| {
+                    let path = self.project.root.join(f);
+                    path.is_dir()
+                        || path.is_file()
+                            && f.extension()
+                                .and_then(|e| e.to_str())
+                                .is_some_and(|e| extensions.contains(&e))
+                })
+                .cloned()
+                .collect(),
+        )
+    }
+
+    /// Changed JS/TS sources; see `files_with_extensions`.
+    pub fn js_files(&self) -> Option<Vec<PathBuf>> {
+        self.files_with_extensions(JS_EXTENSIONS)
+    }
+
+    /// Changed Python sources; see `files_with_extensions`.
+    pub fn py_files(&self) -> Option<Vec<PathBuf>> {
+        self.files_with_extensions(PY_EXTENSIONS)
+    }
+}
+
+// TS-001: Cow<'static, str> because built-in tool names are compile-time
+// constants while `custom_tools` names come from config.
 #[derive(Debug)]
 pub struct ToolResult {
-    pub name: &'static str,
+    pub name: Cow<'static, str>,
     pub output: String,
     pub success: bool,
+    /// Structured findings; `None` when the tool has no parser or its output
+    /// could not be parsed, in which case `output` is shown verbatim.
+    pub diagnostics: Option<Vec<Diagnostic>>,
 }
 
 impl ToolResult {
-    pub fn skipped(name: &'static str) -> Self {
+    pub fn skipped(name: impl Into<Cow<'static, str>>) -> Self {
         Self {
-            name,
+            name: name.into(),
             output: String::new(),
             success: false,
+            diagnostics: None,
+        }
+    }
+
+    /// Prefixes relative diagnostic paths with `dir`, for tools that ran in a
+    /// subdirectory of the project root.
+    pub fn rebase(&mut self, dir: &Path) {
+        for d in self.diagnostics.iter_mut().flatten() {
+            if let Some(file) = &d.file
+                && file.is_relative()
+            {
+                d.file = Some(dir.join(file));
+            }
         }
     }
 }
 
+/// Parses the primary output stream: stdout, or stderr for tools that only
+/// write diagnostics there. Stamps the tool name onto each diagnostic.
+fn parse_output(
+    name: Cow<'static, str>,
+    output: &Output,
+    parse: Parser,
+) -> Option<Vec<Diagnostic>> {
+    let stream = if output.stdout.is_empty() {
+        &output.stderr
+    } else {
+        &output.stdout
+    };
+    let text = sanitize::sanitize(&String::from_utf8_lossy(stream));
+    let mut diagnostics = parse(&text)?;
+    for d in &mut diagnostics {
+        d.tool = name.clone();
+    }
+    Some(diagnostics)
+}
+
 fn combine_output(output: &Output) -> String {
     let stdout = String::from_utf8_lossy(&output.stdout);
     let stderr = String::from_utf8_lossy(&output.stderr);
@@ -64,12 +485,43 @@ fn truncate_output(s: &str) -> String {
 }
 
 /// Aggregate output budget: truncate results that would exceed MAX_TOTAL_OUTPUT.
+/// Parsed results are measured by their rendered findings rather than the raw
+/// output, and are each capped at MAX_OUTPUT_SIZE like raw output.
 pub fn enforce_total_budget(results: &mut [ToolResult]) {
     let mut total = 0usize;
     for result in results.iter_mut() {
-        total += result.output.len();
-        if total > MAX_TOTAL_OUTPUT {
+        let Some(diagnostics) = &mut result.diagnostics else {
+            total += result.output.len();
+            if total > MAX_TOTAL_OUTPUT {
+                result.output = "[omitted: total output budget exceeded]".into();
+            }
+            continue;
+        };
+        let budget = MAX_OUTPUT_SIZE.min(MAX_TOTAL_OUTPUT.saturating_sub(total));
+        let mut used = 0;
+        let mut kept = 0;
+        for d in diagnostics.iter() {
+            // Rendered as `- {d}\n`.
+            let len = d.to_string().len() + 3;
+            if used + len > budget {
+                break;
+            }
+            used += len;
+            kept += 1;
+        }
+        total += used;
+        let omitted = diagnostics.len() - kept;
+        if kept == 0 && omitted > 0 {
             result.output = "[omitted: total output budget exceeded]".into();
+            result.diagnostics = None;
+        } else if omitted > 0 {
+            diagnostics.truncate(kept);
+            diagnostics.push(Diagnostic {
+                tool: result.name.clone(),
+                severity: Severity::Info,
+                message: format!("{omitted} more findings omitted: output budget exceeded"),
+                ..Default::default()
+            });
         }
     }
 }
@@ -87,10 +539,12 @@ fn kill_process_group(pid: u32) {
 }
 
 fn run_with_timeout_duration(
-    name: &'static str,
+    name: impl Into<Cow<'static, str>>,
     mut cmd: Command,
     timeout: Duration,
+    parse: Option<Parser>,
 ) -> ToolResult {
+    let name = name.into();
     cmd.process_group(0);
 
     let child = match cmd.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn() {
@@ -111,6 +565,7 @@ fn run_with_timeout_duration(
 
     match rx.recv_timeout(timeout) {
         Ok(Ok(output)) => ToolResult {
+            diagnostics: parse.and_then(|p| parse_output(name.clone(), &output, p)),
             name,
             success: output.status.success(),
             output: combine_output(&output),
@@ -135,24 +590,59 @@ fn run_with_timeout_duration(
     }
 }
 
-fn run_with_timeout(name: &'static str, cmd: Command) -> ToolResult {
-    run_with_timeout_duration(name, cmd, TOOL_TIMEOUT)
+#[cfg(test)]
+fn run_with_timeout(name: &'static str, cmd: Command, parse: Option<Parser>) -> ToolResult {
+    run_with_timeout_duration(name, cmd, TOOL_TIMEOUT, parse)
+}
+
+/// First line of `bin --version`, or `None` when it fails or prints nothing.
+pub fn version(name: &str, bin: &Path) -> Option<String> {
+    let mut cmd = Command::new(bin);
+    cmd.arg("--version");
+    let result = run_with_timeout_duration(name.to_string(), cmd, VERSION_TIMEOUT, None);
+    if !result.success {
+        return None;
+    }
+    result
+        .output
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .map(str::to_string)
 }
 
-pub(crate) fn run_js_command(
-    name: &'static str,
+/// Runs `bin` in the project root with `args`, then the configured extra
+/// arguments, then `files`. Honors the configured timeout and environment.
+pub(crate) fn run_command(
+    name: impl Into<Cow<'static, str>>,
     bin: &Path,
     args: &[&str],
-    info: &crate::project::ProjectInfo,
+    files: &[PathBuf],
+    ctx: &ToolContext,
+    parse: Parser,
 ) -> ToolResult {
+    let root = &ctx.project.root;
+    let settings = &ctx.settings;
     let mut cmd = Command::new(bin);
-    cmd.args(args).current_dir(&info.root);
-    run_with_timeout(name, cmd)
+    cmd.args(args)
+        .args(&settings.args)
+        .args(files)
+        .envs(&settings.env)
+        .current_dir(root);
+    let timeout = settings.timeout.unwrap_or(TOOL_TIMEOUT);
+    let mut result = run_with_timeout_duration(name, cmd, timeout, Some(parse));
+    if let Some(diagnostics) = &mut result.diagnostics {
+        for d in diagnostics {
+            d.relativize(root);
+        }
+    }
+    result
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::os::unix::fs::PermissionsExt;
     use std::os::unix::process::ExitStatusExt;
     use std::process::{ExitStatus, Output};
 
@@ -211,7 +701,7 @@ mod tests {
     fn run_with_timeout_success() {
         let mut cmd = Command::new("echo");
         cmd.arg("hello");
-        let result = run_with_timeout("echo-test", cmd);
+        let result = run_with_timeout("echo-test", cmd, None);
         assert!(result.success);
         assert!(result.output.contains("hello"));
     }
@@ -219,7 +709,7 @@ mod tests {
     #[test]
     fn run_with_timeout_handles_missing_command() {
         let cmd = Command::new("nonexistent-command-12345");
-        let result = run_with_timeout("missing", cmd);
+        let result = run_with_timeout("missing", cmd, None);
         assert!(!result.success);
         assert!(result.output.is_empty());
     }
@@ -228,7 +718,7 @@ mod tests {
     fn run_with_timeout_captures_exit_code() {
         let mut cmd = Command::new("sh");
         cmd.args(["-c", "echo fail >&2; exit 1"]);
-        let result = run_with_timeout("fail-test", cmd);
+        let result = run_with_timeout("fail-test", cmd, None);
         assert!(!result.success);
         assert!(result.output.contains("fail"));
     }
@@ -237,43 +727,225 @@ mod tests {
     fn run_with_timeout_duration_kills_on_timeout() {
         let mut cmd = Command::new("sleep");
         cmd.arg("120");
-        let result = run_with_timeout_duration("sleep-test", cmd, Duration::from_millis(200));
+        let result = run_with_timeout_duration("sleep-test", cmd, Duration::from_millis(200), None);
         assert!(!result.success);
         assert!(result.output.is_empty());
         assert_eq!(result.name, "sleep-test");
     }
 
+    #[test]
+    fn version_reads_first_line() {
+        let tmp = crate::test_utils::TempDir::new("tools-version");
+        let bin = tmp.join("fake-tool");
+        std::fs::write(&bin, "#!/bin/sh\necho\necho 'fake 1.2.3'\necho extra\n").unwrap();
+        std::fs::set_permissions(&bin, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert_eq!(version("fake", &bin).as_deref(), Some("fake 1.2.3"));
+        assert_eq!(
+            version("missing", Path::new("nonexistent-command-12345")),
+            None
+        );
+    }
+
+    #[test]
+    fn run_with_timeout_parses_stdout() {
+        let mut cmd = Command::new("sh");
+        cmd.args([
+            "-c",
+            "echo 'src/a.ts(1,2): error TS1005: x'; echo noise >&2; exit 2",
+        ]);
+        let result = run_with_timeout("parse-test", cmd, Some(tsgo::parse));
+        let diags = result.diagnostics.unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].tool, "parse-test");
+        assert!(result.output.contains("noise"));
+    }
+
+    #[test]
+    fn parse_output_falls_back_to_stderr() {
+        let output = Output {
+            status: ExitStatus::from_raw(0),
+            stdout: vec![],
+            stderr: b"error TS5083: Cannot read file".to_vec(),
+        };
+        let diags = parse_output("tsgo".into(), &output, tsgo::parse).unwrap();
+        assert_eq!(diags[0].rule.as_deref(), Some("TS5083"));
+    }
+
+    #[test]
+    fn unparseable_output_keeps_raw() {
+        let mut cmd = Command::new("echo");
+        cmd.arg("not json");
+        let result = run_with_timeout("raw-test", cmd, Some(oxlint::parse));
+        assert!(result.diagnostics.is_none());
+        assert_eq!(result.output, "not json");
+    }
+
+    #[test]
+    fn run_command_appends_files() {
+        let tmp = crate::test_utils::TempDir::new("tools-files");
+        let ctx = ToolContext::new(ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        });
+        let files = [PathBuf::from("src/a.ts"), PathBuf::from("b.tsx")];
+        let result = run_command(
+            "echo",
+            Path::new("echo"),
+            &["--format", "json"],
+            &files,
+            &ctx,
+            oxlint::parse,
+        );
+        assert_eq!(result.output, "--format json src/a.ts b.tsx");
+    }
+
+    #[test]
+    fn run_command_applies_settings() {
+        let tmp = crate::test_utils::TempDir::new("tools-settings");
+        let mut ctx = ToolContext::new(ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        });
+        ctx.settings.args = vec!["-p".into(), "tsconfig.app.json".into()];
+        ctx.settings
+            .env
+            .insert("REVIEWS_TEST_VAR".into(), "set".into());
+        let result = run_command(
+            "sh",
+            Path::new("sh"),
+            &["-c", "echo \"$REVIEWS_TEST_VAR $*\"", "sh", "--noEmit"],
+            &[],
+            &ctx,
+            oxlint::parse,
+        );
+        assert_eq!(result.output, "set --noEmit -p tsconfig.app.json");
+
+        ctx.settings.args.clear();
+        ctx.settings.timeout = Some(Duration::from_millis(200));
+        let result = run_command(
+            "sleep",
+            Path::new("sleep"),
+            &["120"],
+            &[],
+            &ctx,
+            oxlint::parse,
+        );
+        assert!(!result.success);
+        assert!(result.output.is_empty());
+    }
+
+    #[test]
+    fn js_files_filters_by_extension_and_existence() {
+        let tmp = crate::test_utils::TempDir::new("tools-jsfiles");
+        std::fs::write(tmp.join("a.ts"), "").unwrap();
+        std::fs::write(tmp.join("b.md"), "").unwrap();
+        std::fs::create_dir(tmp.join("src")).unwrap();
+        let mut ctx = ToolContext::new(ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        });
+        assert!(ctx.js_files().is_none());
+
+        ctx.files = Some(vec!["a.ts".into(), "b.md".into(), "deleted.ts".into()]);
+        assert_eq!(ctx.js_files().unwrap(), vec![PathBuf::from("a.ts")]);
+
+        ctx.files = Some(vec!["src".into(), "missing".into()]);
+        assert_eq!(ctx.js_files().unwrap(), vec![PathBuf::from("src")]);
+
+        ctx.files = Some(vec![PathBuf::from("x.ts"); MAX_FILE_ARGS + 1]);
+        assert!(ctx.js_files().is_none());
+    }
+
     #[test]
     fn enforce_total_budget_truncates_excess() {
         let mut results = vec![
             ToolResult {
-                name: "a",
+                name: "a".into(),
                 output: "x".repeat(MAX_TOTAL_OUTPUT),
                 success: true,
+                diagnostics: None,
             },
             ToolResult {
-                name: "b",
+                name: "b".into(),
                 output: "overflow".into(),
                 success: true,
+                diagnostics: None,
             },
         ];
+        results[1].diagnostics = Some(vec![Diagnostic::default()]);
         enforce_total_budget(&mut results);
         assert_eq!(results[0].output.len(), MAX_TOTAL_OUTPUT);
         assert!(results[1].output.contains("budget exceeded"));
+        assert!(results[1].diagnostics.is_none());
+    }
+
+    #[test]
+    fn enforce_total_budget_caps_rendered_diagnostics() {
+        let parsed = |name: &'static str| ToolResult {
+            name: name.into(),
+            // Raw output is truncated to MAX_OUTPUT_SIZE, but the parsed
+            // findings come from the full stream.
+            output: "x".repeat(MAX_OUTPUT_SIZE),
+            success: false,
+            diagnostics: Some(
+                (0..5_000)
+                    .map(|i| Diagnostic {
+                        file: Some(format!("src/file{i}.ts").into()),
+                        line: Some(1),
+                        message: "m".repeat(60),
+                        ..Default::default()
+                    })
+                    .collect(),
+            ),
+        };
+        let mut results = vec![parsed("a"), parsed("b"), parsed("c")];
+        enforce_total_budget(&mut results);
+
+        let rendered = |r: &ToolResult| -> usize {
+            let diagnostics = r.diagnostics.as_ref().unwrap();
+            diagnostics.iter().map(|d| d.to_string().len() + 3).sum()
+        };
+        let total: usize = results
+            .iter()
+            .filter(|r| r.diagnostics.is_some())
+            .map(rendered)
+            .sum();
+        assert!(total <= MAX_TOTAL_OUTPUT + 3 * 100);
+        for r in &results {
+            if r.diagnostics.is_none() {
+                assert!(r.output.contains("budget exceeded"));
+                continue;
+            }
+            assert!(rendered(r) <= MAX_OUTPUT_SIZE + 100);
+            let last = r.diagnostics.as_ref().unwrap().last().unwrap();
+            assert_eq!(last.severity, Severity::Info);
+            assert!(
+                last.message
+                    .ends_with("more findings omitted: output budget exceeded")
+            );
+        }
+        assert!(
+            results[2]
+                .diagnostics
+                .as_ref()
+                .is_none_or(|d| d.len() < 100)
+        );
     }
 
     #[test]
     fn enforce_total_budget_no_truncation_when_within_limit() {
         let mut results = vec![
             ToolResult {
-                name: "a",
+                name: "a".into(),
                 output: "small".into(),
                 success: true,
+                diagnostics: None,
             },
             ToolResult {
-                name: "b",
+                name: "b".into(),
                 output: "also small".into(),
                 success: true,
+                diagnostics: None,
             },
         ];
         enforce_total_budget(&mut results);
@@ -0,0 +1,140 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use regex::Regex;
+use serde_json::Value;
+use std::sync::LazyLock;
+
+/// `app/main.py:3:5: error: message  [code]`; the column is optional.
+static TEXT_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?P<file>[^:]+):(?P<line>\d+):(?:(?P<col>\d+):)? (?P<sev>error|warning|note): (?P<msg>.*?)(?:  \[(?P<code>[a-z0-9-]+)\])?$",
+    )
+    .unwrap()
+});
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_python
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("mypy");
+    }
+
+    let bin = resolve::resolve_bin("mypy", &ctx.project.root);
+    super::run_command("mypy", &bin, &["--output", "json", "."], &[], ctx, parse)
+}
+
+/// Parses mypy's JSON lines (`--output json`, mypy 1.11+) or, for older
+/// versions and user-supplied arguments, its default text format. Notes on
+/// the same line as the preceding finding become its fix hint.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut recognized = false;
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for line in output.lines() {
+        let Some(d) = parse_json(line).or_else(|| parse_text(line)) else {
+            recognized |= line.starts_with("Success: ") || line.starts_with("Found ");
+            continue;
+        };
+        recognized = true;
+        if d.severity == Severity::Info
+            && let Some(prev) = diagnostics.last_mut()
+            && prev.file == d.file
+            && prev.line == d.line
+        {
+            match &mut prev.fix {
+                Some(fix) => {
+                    fix.push(' ');
+                    fix.push_str(&d.message);
+                }
+                None => prev.fix = Some(d.message),
+            }
+            continue;
+        }
+        diagnostics.push(d);
+    }
+    recognized.then_some(diagnostics)
+}
+
+fn parse_json(line: &str) -> Option<Diagnostic> {
+    let json: Value = serde_json::from_str(line).ok()?;
+    // mypy reports 0-based columns and -1 when unknown.
+    let column = json
+        .get("column")
+        .and_then(|c| c.as_i64())
+        .filter(|c| *c >= 0)
+        .and_then(|c| u32::try_from(c + 1).ok());
+    Some(Diagnostic {
+        rule: diagnostic::json_string(&json, "code"),
+        severity: json
+            .get("severity")
+            .and_then(|s| s.as_str())
+            .map_or(Severity::Error, Severity::from_label),
+        file: diagnostic::json_string(&json, "file").map(Into::into),
+        line: diagnostic::json_u32(&json, "line"),
+        column,
+        message: diagnostic::json_string(&json, "message")?,
+        fix: diagnostic::json_string(&json, "hint"),
+        ..Default::default()
+    })
+}
+
+fn parse_text(line: &str) -> Option<Diagnostic> {
+    let caps = TEXT_RE.captures(line)?;
+    Some(Diagnostic {
+        rule: caps.name("code").map(|m| m.as_str().to_string()),
+        severity: Severity::from_label(&caps["sev"]),
+        file: Some(caps["file"].into()),
+        line: caps["line"].parse().ok(),
+        column: caps.name("col").and_then(|m| m.as_str().parse().ok()),
+        message: caps["msg"].to_string(),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_json_lines() {
+        let output = concat!(
+            r#"{"file": "app/main.py", "line": 3, "column": 4, "message": "Argument 1 has incompatible type \"str\"; expected \"int\"", "hint": null, "code": "arg-type", "severity": "error"}"#,
+            "\n",
+            r#"{"file": "app/util.py", "line": 10, "column": -1, "message": "Missing return statement", "hint": "Add a return", "code": "return", "severity": "error"}"#,
+            "\nFound 2 errors in 2 files (checked 5 source files)\n",
+        );
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].rule.as_deref(), Some("arg-type"));
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("app/main.py")));
+        assert_eq!((diags[0].line, diags[0].column), (Some(3), Some(5)));
+        assert_eq!(diags[1].column, None);
+        assert_eq!(diags[1].fix.as_deref(), Some("Add a return"));
+    }
+
+    #[test]
+    fn parses_text_output_with_notes() {
+        let output = "app/main.py:3: error: Name \"foo\" is not defined  [name-defined]\n\
+app/main.py:3: note: Did you mean \"for\"?\n\
+app/util.py:7:12: warning: Unused \"type: ignore\" comment  [unused-ignore]\n\
+Found 2 errors in 2 files (checked 5 source files)\n";
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].rule.as_deref(), Some("name-defined"));
+        assert_eq!(diags[0].message, "Name \"foo\" is not defined");
+        assert_eq!(diags[0].fix.as_deref(), Some("Did you mean \"for\"?"));
+        assert_eq!(diags[1].severity, Severity::Warning);
+        assert_eq!(diags[1].column, Some(12));
+    }
+
+    #[test]
+    fn clean_run_and_usage_errors() {
+        let clean = "Success: no issues found in 5 source files\n";
+        assert_eq!(parse(clean), Some(vec![]));
+        assert!(parse("mypy: error: unrecognized arguments: --output").is_none());
+    }
+}
@@ -1,26 +1,116 @@
-use super::ToolResult;
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
 use crate::project::ProjectInfo;
 use crate::resolve;
+use serde_json::Value;
 
-pub fn run(project: &ProjectInfo) -> ToolResult {
-    let bin = resolve::resolve_bin("oxlint", &project.root);
-    super::run_js_command("oxlint", &bin, &["--format", "json"], project)
+/// oxlint needs no project configuration.
+pub fn applies(_project: &ProjectInfo) -> bool {
+    true
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    let files = match ctx.js_files() {
+        Some(files) if files.is_empty() => return ToolResult::skipped("oxlint"),
+        Some(files) => files,
+        None => Vec::new(),
+    };
+
+    let bin = resolve::resolve_bin("oxlint", &ctx.project.root);
+    super::run_command("oxlint", &bin, &["--format", "json"], &files, ctx, parse)
+}
+
+/// Parses `oxlint --format json`. Accepts both the current
+/// `{"diagnostics": [...]}` envelope and the older bare array.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output).ok()?;
+    let items = match &json {
+        Value::Array(items) => items,
+        other => other.get("diagnostics")?.as_array()?,
+    };
+    Some(items.iter().filter_map(parse_item).collect())
+}
+
+fn parse_item(item: &Value) -> Option<Diagnostic> {
+    let span = item
+        .get("labels")
+        .and_then(|l| l.as_array())
+        .and_then(|l| l.first())
+        .and_then(|l| l.get("span"));
+    Some(Diagnostic {
+        rule: diagnostic::json_string(item, "code"),
+        severity: item
+            .get("severity")
+            .and_then(|s| s.as_str())
+            .map_or(Severity::Warning, Severity::from_label),
+        file: diagnostic::json_string(item, "filename").map(Into::into),
+        line: span.and_then(|s| diagnostic::json_u32(s, "line")),
+        column: span.and_then(|s| diagnostic::json_u32(s, "column")),
+        message: diagnostic::json_string(item, "message")?,
+        fix: diagnostic::json_string(item, "help"),
+        ..Default::default()
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn runs_without_package_json() {
         let info = ProjectInfo {
             root: PathBuf::from("/tmp/nonexistent"),
-            has_package_json: false,
-            has_tsconfig: false,
-            has_react: false,
+            ..Default::default()
         };
-        let result = run(&info);
+        let result = run(&ToolContext::new(info));
         assert_eq!(result.name, "oxlint");
     }
+
+    #[test]
+    fn skips_when_no_changed_js_files() {
+        let info = ProjectInfo {
+            root: PathBuf::from("/tmp/nonexistent"),
+            has_package_json: true,
+            ..Default::default()
+        };
+        let mut ctx = ToolContext::new(info);
+        ctx.files = Some(vec!["README.md".into()]);
+        let result = run(&ctx);
+        assert!(!result.success);
+        assert!(result.output.is_empty());
+    }
+
+    #[test]
+    fn parses_diagnostics_envelope() {
+        let output = r#"{"diagnostics": [{
+            "message": "'x' is declared but never used.",
+            "code": "eslint(no-unused-vars)",
+            "severity": "warning",
+            "help": "Consider removing this declaration.",
+            "filename": "src/a.ts",
+            "labels": [{"span": {"offset": 6, "length": 1, "line": 1, "column": 7}}]
+        }], "number_of_files": 1}"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 1);
+        let d = &diags[0];
+        assert_eq!(d.rule.as_deref(), Some("eslint(no-unused-vars)"));
+        assert_eq!(d.severity, Severity::Warning);
+        assert_eq!(d.file.as_deref(), Some(Path::new("src/a.ts")));
+        assert_eq!((d.line, d.column), (Some(1), Some(7)));
+        assert!(d.fix.as_deref().unwrap().contains("removing"));
+    }
+
+    #[test]
+    fn parses_legacy_array() {
+        let output = r#"[{"message": "bad", "severity": "error", "filename": "a.js"}]"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert!(diags[0].line.is_none());
+    }
+
+    #[test]
+    fn rejects_non_json() {
+        assert!(parse("Finished in 3ms").is_none());
+    }
 }
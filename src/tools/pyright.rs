@@ -0,0 +1,83 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_python
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("pyright");
+    }
+
+    let bin = resolve::resolve_bin("pyright", &ctx.project.root);
+    super::run_command("pyright", &bin, &["--outputjson"], &[], ctx, parse)
+}
+
+/// Parses `pyright --outputjson`, converting its 0-based ranges to 1-based.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    let items = json.get("generalDiagnostics")?.as_array()?;
+    Some(items.iter().filter_map(parse_item).collect())
+}
+
+fn parse_item(item: &Value) -> Option<Diagnostic> {
+    let range = item.get("range");
+    let position = |edge: &str, key: &str| {
+        range
+            .and_then(|r| r.get(edge))
+            .and_then(|p| diagnostic::json_u32(p, key))
+            .map(|n| n + 1)
+    };
+    Some(Diagnostic {
+        rule: diagnostic::json_string(item, "rule"),
+        severity: item
+            .get("severity")
+            .and_then(|s| s.as_str())
+            .map_or(Severity::Error, Severity::from_label),
+        file: diagnostic::json_string(item, "file").map(Into::into),
+        line: position("start", "line"),
+        column: position("start", "character"),
+        end_line: position("end", "line"),
+        end_column: position("end", "character"),
+        message: diagnostic::json_string(item, "message")?,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_pyright_json() {
+        let output = r#"{
+            "version": "1.1.380",
+            "generalDiagnostics": [
+                {"file": "/repo/app/main.py", "severity": "error",
+                 "message": "Import \"requests\" could not be resolved",
+                 "range": {"start": {"line": 2, "character": 7}, "end": {"line": 2, "character": 15}},
+                 "rule": "reportMissingImports"},
+                {"file": "/repo/app/main.py", "severity": "information", "message": "Unused expression",
+                 "range": {"start": {"line": 9, "character": 0}, "end": {"line": 9, "character": 3}}}
+            ],
+            "summary": {"filesAnalyzed": 1, "errorCount": 1}
+        }"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].rule.as_deref(), Some("reportMissingImports"));
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!((diags[0].line, diags[0].column), (Some(3), Some(8)));
+        assert_eq!(diags[0].end_column, Some(16));
+        assert_eq!(diags[1].severity, Severity::Info);
+        assert_eq!(diags[1].rule, None);
+    }
+
+    #[test]
+    fn rejects_non_json() {
+        assert!(parse("No configuration file found.").is_none());
+    }
+}
@@ -1,31 +1,133 @@
-use super::ToolResult;
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{Diagnostic, Severity};
 use crate::project::ProjectInfo;
 use crate::resolve;
+use regex::Regex;
+use std::sync::LazyLock;
 
-pub fn run(project: &ProjectInfo) -> ToolResult {
-    if !project.has_react {
+static LOCATION_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?P<file>[\w./@\[\]()-]+\.(?:tsx|ts|jsx|js|mjs|cjs)):(?P<line>\d+)(?::(?P<col>\d+))?",
+    )
+    .unwrap()
+});
+static RULE_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\s*[(\[](?P<rule>[a-z0-9@/-]+)[)\]]$").unwrap());
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_react
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    let project = &ctx.project;
+    if !applies(project) {
         return ToolResult::skipped("react-doctor");
     }
 
     let bin = resolve::resolve_bin("react-doctor", &project.root);
-    super::run_js_command("react-doctor", &bin, &[".", "--verbose"], project)
+    super::run_command("react-doctor", &bin, &[".", "--verbose"], &[], ctx, parse)
+}
+
+fn severity_of(text: &str) -> Severity {
+    let lower = text.to_ascii_lowercase();
+    if lower.contains("error") || text.contains(['✗', '✖', '×']) {
+        Severity::Error
+    } else {
+        Severity::Warning
+    }
+}
+
+/// Parses react-doctor's verbose text report. Each `file:line[:col]` becomes a
+/// diagnostic; its message is the text after the location, or the nearest
+/// preceding heading line when the location stands alone.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut diagnostics = Vec::new();
+    let mut heading: Option<&str> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        let Some(caps) = LOCATION_RE.captures(trimmed) else {
+            heading = Some(trimmed);
+            continue;
+        };
+        let whole = caps.get(0).unwrap();
+        let after = trimmed[whole.end()..].trim_start_matches([' ', ':', '-', '–', '—']);
+        let (context, text) = if after.is_empty() {
+            (heading.unwrap_or(trimmed), heading.unwrap_or_default())
+        } else {
+            (trimmed, after)
+        };
+        let text = text.trim_start_matches(['✗', '✖', '×', '⚠', '!', ' ']);
+        if text.is_empty() {
+            continue;
+        }
+        let (message, rule) = match RULE_RE.captures(text) {
+            Some(r) => (
+                text[..r.get(0).unwrap().start()].to_string(),
+                Some(r["rule"].to_string()),
+            ),
+            None => (text.to_string(), None),
+        };
+        diagnostics.push(Diagnostic {
+            rule,
+            severity: severity_of(context),
+            file: Some(caps["file"].into()),
+            line: caps["line"].parse().ok(),
+            column: caps.name("col").and_then(|c| c.as_str().parse().ok()),
+            message,
+            ..Default::default()
+        });
+    }
+
+    if diagnostics.is_empty() && !output.trim().is_empty() {
+        return None;
+    }
+    Some(diagnostics)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn skips_without_react() {
         let info = ProjectInfo {
             root: PathBuf::from("/tmp/nonexistent"),
             has_package_json: true,
-            has_tsconfig: false,
-            has_react: false,
+            ..Default::default()
         };
-        let result = run(&info);
+        let result = run(&ToolContext::new(info));
         assert!(!result.success);
         assert!(result.output.is_empty());
     }
+
+    #[test]
+    fn parses_heading_followed_by_locations() {
+        let output =
+            "✗ Array index used as key (no-array-index-key)\n  src/List.tsx:12:8\n  src/Grid.tsx:4";
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("src/List.tsx")));
+        assert_eq!((diags[0].line, diags[0].column), (Some(12), Some(8)));
+        assert_eq!(diags[0].rule.as_deref(), Some("no-array-index-key"));
+        assert_eq!(diags[0].message, "Array index used as key");
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(diags[1].column, None);
+    }
+
+    #[test]
+    fn parses_inline_message() {
+        let diags = parse("⚠ src/App.jsx:3 - useEffect has a missing dependency").unwrap();
+        assert_eq!(diags[0].message, "useEffect has a missing dependency");
+        assert_eq!(diags[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn score_only_output_falls_back() {
+        assert!(parse("React Doctor score: 92/100").is_none());
+    }
 }
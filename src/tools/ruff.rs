@@ -0,0 +1,104 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_python
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("ruff");
+    }
+    let files = match ctx.py_files() {
+        Some(files) if files.is_empty() => return ToolResult::skipped("ruff"),
+        Some(files) => files,
+        None => Vec::new(),
+    };
+
+    let bin = resolve::resolve_bin("ruff", &ctx.project.root);
+    super::run_command(
+        "ruff",
+        &bin,
+        &["check", "--output-format", "json"],
+        &files,
+        ctx,
+        parse,
+    )
+}
+
+/// Parses `ruff check --output-format json`. Syntax errors carry no rule
+/// code and are reported as errors; lint violations as warnings.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output.trim()).ok()?;
+    Some(json.as_array()?.iter().filter_map(parse_item).collect())
+}
+
+fn parse_item(item: &Value) -> Option<Diagnostic> {
+    let rule = diagnostic::json_string(item, "code");
+    let start = item.get("location");
+    let end = item.get("end_location");
+    Some(Diagnostic {
+        severity: if rule.is_some() {
+            Severity::Warning
+        } else {
+            Severity::Error
+        },
+        rule,
+        file: diagnostic::json_string(item, "filename").map(Into::into),
+        line: start.and_then(|l| diagnostic::json_u32(l, "row")),
+        column: start.and_then(|l| diagnostic::json_u32(l, "column")),
+        end_line: end.and_then(|l| diagnostic::json_u32(l, "row")),
+        end_column: end.and_then(|l| diagnostic::json_u32(l, "column")),
+        message: diagnostic::json_string(item, "message")?,
+        fix: item
+            .get("fix")
+            .and_then(|f| diagnostic::json_string(f, "message")),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_ruff_json() {
+        let output = r#"[
+            {"code": "F401", "message": "`os` imported but unused", "filename": "/repo/app/main.py",
+             "location": {"row": 1, "column": 8}, "end_location": {"row": 1, "column": 10},
+             "fix": {"applicability": "safe", "message": "Remove unused import: `os`", "edits": []},
+             "url": "https://docs.astral.sh/ruff/rules/unused-import", "noqa_row": 1},
+            {"code": null, "message": "SyntaxError: Expected an expression", "filename": "/repo/app/bad.py",
+             "location": {"row": 4, "column": 5}, "end_location": {"row": 4, "column": 6}, "fix": null}
+        ]"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].rule.as_deref(), Some("F401"));
+        assert_eq!(diags[0].severity, Severity::Warning);
+        assert_eq!(
+            diags[0].file.as_deref(),
+            Some(Path::new("/repo/app/main.py"))
+        );
+        assert_eq!((diags[0].line, diags[0].column), (Some(1), Some(8)));
+        assert_eq!(diags[0].fix.as_deref(), Some("Remove unused import: `os`"));
+        assert_eq!(diags[1].rule, None);
+        assert_eq!(diags[1].severity, Severity::Error);
+    }
+
+    #[test]
+    fn empty_array_and_text() {
+        assert_eq!(parse("[]"), Some(vec![]));
+        assert!(parse("error: unexpected argument").is_none());
+    }
+
+    #[test]
+    fn skips_without_python_project() {
+        let result = run(&ToolContext::new(ProjectInfo::default()));
+        assert_eq!(result.name, "ruff");
+        assert!(result.output.is_empty());
+    }
+}
@@ -0,0 +1,90 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_go_mod
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("staticcheck");
+    }
+
+    let bin = resolve::resolve_bin("staticcheck", &ctx.project.root);
+    super::run_command(
+        "staticcheck",
+        &bin,
+        &["-f", "json", "./..."],
+        &[],
+        ctx,
+        parse,
+    )
+}
+
+/// Parses `staticcheck -f json`: one JSON object per line. Problems
+/// suppressed by `//lint:ignore` are reported with severity `ignored` and
+/// dropped.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut recognized = output.trim().is_empty();
+    let mut diagnostics = Vec::new();
+    for line in output.lines() {
+        let Ok(item) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        recognized = true;
+        let severity = item.get("severity").and_then(|s| s.as_str());
+        if severity == Some("ignored") {
+            continue;
+        }
+        let start = item.get("location");
+        let end = item.get("end");
+        let Some(message) = diagnostic::json_string(&item, "message") else {
+            continue;
+        };
+        diagnostics.push(Diagnostic {
+            rule: diagnostic::json_string(&item, "code"),
+            severity: severity.map_or(Severity::Warning, Severity::from_label),
+            file: start
+                .and_then(|l| diagnostic::json_string(l, "file"))
+                .map(Into::into),
+            line: start.and_then(|l| diagnostic::json_u32(l, "line")),
+            column: start.and_then(|l| diagnostic::json_u32(l, "column")),
+            end_line: end.and_then(|l| diagnostic::json_u32(l, "line")),
+            end_column: end.and_then(|l| diagnostic::json_u32(l, "column")),
+            message,
+            ..Default::default()
+        });
+    }
+    recognized.then_some(diagnostics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_json_lines() {
+        let output = concat!(
+            r#"{"code":"SA4006","severity":"error","location":{"file":"/repo/main.go","line":10,"column":2},"end":{"file":"/repo/main.go","line":10,"column":5},"message":"this value of err is never used"}"#,
+            "\n",
+            r#"{"code":"ST1005","severity":"ignored","location":{"file":"/repo/main.go","line":3,"column":1},"message":"error strings should not be capitalized"}"#,
+            "\n",
+        );
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].rule.as_deref(), Some("SA4006"));
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("/repo/main.go")));
+        assert_eq!(diags[0].end_column, Some(5));
+    }
+
+    #[test]
+    fn clean_and_unrecognized_output() {
+        assert_eq!(parse(""), Some(vec![]));
+        assert!(parse("-: go: cannot find main module").is_none());
+    }
+}
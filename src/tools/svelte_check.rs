@@ -0,0 +1,95 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// `1590680326283 ERROR "src/App.svelte" 1:16 "message"`
+static PROBLEM_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"^\d+ (?P<sev>ERROR|WARNING) (?P<file>"(?:[^"\\]|\\.)*") (?P<line>\d+):(?P<col>\d+) (?P<msg>".*")$"#)
+        .unwrap()
+});
+/// `1590680325583 START "/path"` and `... COMPLETED 20 FILES 1 ERRORS ...`
+static FRAME_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\d+ (?:START|COMPLETED) ").unwrap());
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_svelte
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("svelte-check");
+    }
+
+    let bin = resolve::resolve_bin("svelte-check", &ctx.project.root);
+    super::run_command(
+        "svelte-check",
+        &bin,
+        &["--output", "machine"],
+        &[],
+        ctx,
+        parse,
+    )
+}
+
+/// Parses `svelte-check --output machine`. File names and messages are
+/// JSON-quoted strings.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut recognized = false;
+    let mut diagnostics = Vec::new();
+    for line in output.lines() {
+        if FRAME_RE.is_match(line) {
+            recognized = true;
+        } else if let Some(caps) = PROBLEM_RE.captures(line) {
+            recognized = true;
+            diagnostics.push(Diagnostic {
+                severity: Severity::from_label(&caps["sev"]),
+                file: Some(unquote(&caps["file"]).into()),
+                line: caps["line"].parse().ok(),
+                column: caps["col"].parse().ok(),
+                message: unquote(&caps["msg"]),
+                ..Default::default()
+            });
+        }
+    }
+    recognized.then_some(diagnostics)
+}
+
+fn unquote(quoted: &str) -> String {
+    serde_json::from_str(quoted).unwrap_or_else(|_| quoted.trim_matches('"').to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn parses_machine_output() {
+        let output = r#"1590680325583 START "/repo/app"
+1590680326283 ERROR "src/App.svelte" 1:16 "Type \"number\" is not assignable to type \"string\"."
+1590680326778 WARNING "src/lib/Button.svelte" 3:5 "A11y: <img> element should have an alt attribute"
+1590680326807 COMPLETED 20 FILES 1 ERRORS 1 WARNINGS 2 FILES_WITH_PROBLEMS
+"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("src/App.svelte")));
+        assert_eq!((diags[0].line, diags[0].column), (Some(1), Some(16)));
+        assert_eq!(
+            diags[0].message,
+            r#"Type "number" is not assignable to type "string"."#
+        );
+        assert_eq!(diags[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn clean_and_unrecognized_output() {
+        let clean =
+            "1 START \"/repo\"\n2 COMPLETED 3 FILES 0 ERRORS 0 WARNINGS 0 FILES_WITH_PROBLEMS\n";
+        assert_eq!(parse(clean), Some(vec![]));
+        assert!(parse("Error: Cannot find module 'svelte'").is_none());
+    }
+}
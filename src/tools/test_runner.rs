@@ -0,0 +1,234 @@
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{self, Diagnostic, Severity};
+use crate::project::ProjectInfo;
+use crate::resolve;
+use serde_json::Value;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Test runs are much slower than linting, so they get their own default.
+const TEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+pub fn applies_vitest(project: &ProjectInfo) -> bool {
+    project.dependencies.contains("vitest")
+}
+
+/// Jest runs only when vitest does not, so a project mid-migration is
+/// tested once.
+pub fn applies_jest(project: &ProjectInfo) -> bool {
+    project.dependencies.contains("jest") && !applies_vitest(project)
+}
+
+/// With a diff scope, runs only the tests related to the changed files;
+/// otherwise the whole suite.
+pub fn run_vitest(ctx: &ToolContext) -> ToolResult {
+    if !applies_vitest(&ctx.project) {
+        return ToolResult::skipped("vitest");
+    }
+    let (args, files): (&[&str], _) = match ctx.js_files() {
+        Some(files) if files.is_empty() => return ToolResult::skipped("vitest"),
+        Some(files) => (
+            &["related", "--run", "--reporter=json", "--passWithNoTests"],
+            files,
+        ),
+        None => (&["run", "--reporter=json", "--passWithNoTests"], Vec::new()),
+    };
+    run_tests("vitest", args, &files, ctx)
+}
+
+pub fn run_jest(ctx: &ToolContext) -> ToolResult {
+    if !applies_jest(&ctx.project) {
+        return ToolResult::skipped("jest");
+    }
+    let common = ["--json", "--testLocationInResults", "--passWithNoTests"];
+    let (args, files): (Vec<&str>, Vec<PathBuf>) = match ctx.js_files() {
+        Some(files) if files.is_empty() => return ToolResult::skipped("jest"),
+        Some(files) => ([&common[..], &["--findRelatedTests"]].concat(), files),
+        None => (common.to_vec(), Vec::new()),
+    };
+    run_tests("jest", &args, &files, ctx)
+}
+
+fn run_tests(
+    name: &'static str,
+    args: &[&str],
+    files: &[PathBuf],
+    ctx: &ToolContext,
+) -> ToolResult {
+    let mut ctx = ctx.clone();
+    ctx.settings.timeout.get_or_insert(TEST_TIMEOUT);
+    let bin = resolve::resolve_bin(name, &ctx.project.root);
+    super::run_command(name, &bin, args, files, &ctx, parse)
+}
+
+/// Parses the Jest-compatible JSON report that both runners emit. Each
+/// failing test becomes one finding with its full name and the first line
+/// of the assertion message; suites that failed to run are reported once.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let json: Value = serde_json::from_str(output.trim()).ok().or_else(|| {
+        let line = output.lines().find(|l| l.starts_with('{'))?;
+        serde_json::from_str(line).ok()
+    })?;
+    let suites = json.get("testResults")?.as_array()?;
+
+    let mut diagnostics = Vec::new();
+    for suite in suites {
+        let file = suite.get("name").and_then(|n| n.as_str()).unwrap_or("");
+        let assertions = suite.get("assertionResults").and_then(|a| a.as_array());
+        let failed: Vec<&Value> = assertions
+            .into_iter()
+            .flatten()
+            .filter(|a| a.get("status").and_then(|s| s.as_str()) == Some("failed"))
+            .collect();
+        for assertion in &failed {
+            let failure = assertion
+                .get("failureMessages")
+                .and_then(|m| m.as_array())
+                .and_then(|m| m.first())
+                .and_then(|m| m.as_str())
+                .unwrap_or("");
+            let location = assertion.get("location");
+            let (line, column) = match location.and_then(|l| l.get("line")) {
+                Some(_) => (
+                    location.and_then(|l| diagnostic::json_u32(l, "line")),
+                    location.and_then(|l| diagnostic::json_u32(l, "column")),
+                ),
+                None => stack_location(failure, file).unzip(),
+            };
+            let title = assertion
+                .get("fullName")
+                .or_else(|| assertion.get("title"))
+                .and_then(|t| t.as_str())
+                .unwrap_or("test");
+            diagnostics.push(failure_diagnostic(file, line, column, title, failure));
+        }
+
+        let suite_failed = suite.get("status").and_then(|s| s.as_str()) == Some("failed");
+        let message = suite.get("message").and_then(|m| m.as_str()).unwrap_or("");
+        if suite_failed && failed.is_empty() && !message.trim().is_empty() {
+            diagnostics.push(failure_diagnostic(
+                file,
+                None,
+                None,
+                "Test suite failed to run",
+                message,
+            ));
+        }
+    }
+    Some(diagnostics)
+}
+
+fn failure_diagnostic(
+    file: &str,
+    line: Option<u32>,
+    column: Option<u32>,
+    title: &str,
+    failure: &str,
+) -> Diagnostic {
+    let first_line = failure
+        .lines()
+        .map(str::trim)
+        .find(|l| !l.is_empty())
+        .unwrap_or("");
+    let message = if first_line.is_empty() {
+        title.to_string()
+    } else {
+        format!("{title}: {first_line}")
+    };
+    Diagnostic {
+        rule: Some("test-failure".into()),
+        severity: Severity::Error,
+        file: (!file.is_empty()).then(|| file.into()),
+        line,
+        column,
+        message,
+        ..Default::default()
+    }
+}
+
+/// The first `at <file>:line:col` frame in the failure pointing at the test
+/// file itself.
+fn stack_location(failure: &str, file: &str) -> Option<(u32, u32)> {
+    if file.is_empty() {
+        return None;
+    }
+    failure.lines().find_map(|l| {
+        let rest = &l[l.find(file)? + file.len()..];
+        let mut parts = rest
+            .strip_prefix(':')?
+            .splitn(3, |c: char| !c.is_ascii_digit());
+        let line = parts.next()?.parse().ok()?;
+        let column = parts.next()?.parse().ok()?;
+        Some((line, column))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn vitest_takes_precedence_over_jest() {
+        let mut project = ProjectInfo::default();
+        project.dependencies.insert("jest".into());
+        assert!(applies_jest(&project) && !applies_vitest(&project));
+        project.dependencies.insert("vitest".into());
+        assert!(applies_vitest(&project) && !applies_jest(&project));
+    }
+
+    #[test]
+    fn parses_failing_tests() {
+        let output = r#"{"numFailedTests": 2, "testResults": [
+            {"name": "/repo/src/sum.test.ts", "status": "failed", "message": "", "assertionResults": [
+                {"fullName": "sum adds numbers", "title": "adds numbers", "status": "failed",
+                 "failureMessages": ["AssertionError: expected 3 to be 4 // Object.is equality\n    at /repo/src/sum.test.ts:5:17"]},
+                {"fullName": "sum handles zero", "status": "passed", "failureMessages": []},
+                {"fullName": "sum rejects NaN", "status": "failed", "location": {"line": 12, "column": 3},
+                 "failureMessages": ["Error: expect(received).toThrow()\n\nReceived function did not throw"]}
+            ]},
+            {"name": "/repo/src/broken.test.ts", "status": "failed",
+             "message": "SyntaxError: Unexpected token (3:4)", "assertionResults": []},
+            {"name": "/repo/src/ok.test.ts", "status": "passed", "assertionResults": []}
+        ]}"#;
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 3);
+        assert_eq!(
+            diags[0].message,
+            "sum adds numbers: AssertionError: expected 3 to be 4 // Object.is equality"
+        );
+        assert_eq!(
+            diags[0].file.as_deref(),
+            Some(Path::new("/repo/src/sum.test.ts"))
+        );
+        assert_eq!((diags[0].line, diags[0].column), (Some(5), Some(17)));
+        assert_eq!((diags[1].line, diags[1].column), (Some(12), Some(3)));
+        assert_eq!(
+            diags[1].message,
+            "sum rejects NaN: Error: expect(received).toThrow()"
+        );
+        assert_eq!(
+            diags[2].message,
+            "Test suite failed to run: SyntaxError: Unexpected token (3:4)"
+        );
+    }
+
+    #[test]
+    fn passing_run_and_text() {
+        let passing = r#"{"numFailedTests": 0, "testResults": []}"#;
+        assert_eq!(parse(passing), Some(vec![]));
+        assert!(parse("No test files found, exiting with code 1").is_none());
+    }
+
+    #[test]
+    fn skips_when_no_changed_sources() {
+        let tmp = crate::test_utils::TempDir::new("tests-no-sources");
+        let mut ctx = ToolContext::new(ProjectInfo {
+            root: tmp.to_path_buf(),
+            ..Default::default()
+        });
+        ctx.project.dependencies.insert("vitest".into());
+        ctx.files = Some(vec![]);
+        assert!(run_vitest(&ctx).output.is_empty());
+    }
+}
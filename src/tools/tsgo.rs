@@ -1,31 +1,130 @@
-use super::ToolResult;
+use super::{ToolContext, ToolResult};
+use crate::diagnostic::{Diagnostic, Severity};
 use crate::project::ProjectInfo;
 use crate::resolve;
+use regex::Regex;
+use std::sync::LazyLock;
 
-pub fn run(project: &ProjectInfo) -> ToolResult {
-    if !project.has_tsconfig {
+/// `src/a.ts(3,7): error TS2322: message` (plain) or
+/// `src/a.ts:3:7 - error TS2322: message` (pretty).
+static LOCATED_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"^(?P<file>.+?)(?:\((?P<pl>\d+),(?P<pc>\d+)\):|:(?P<cl>\d+):(?P<cc>\d+) -) (?P<sev>error|warning|message) (?P<code>TS\d+): (?P<msg>.*)$",
+    )
+    .unwrap()
+});
+/// Project-level errors such as `error TS5083: Cannot read file 'tsconfig.json'.`
+static GLOBAL_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<sev>error|warning|message) (?P<code>TS\d+): (?P<msg>.*)$").unwrap()
+});
+
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_tsconfig
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    let project = &ctx.project;
+    if !applies(project) {
         return ToolResult::skipped("tsgo");
     }
 
     let bin = resolve::resolve_bin("tsgo", &project.root);
-    super::run_js_command("tsgo", &bin, &["--noEmit"], project)
+    super::run_command("tsgo", &bin, &["--noEmit"], &[], ctx, parse)
+}
+
+/// Parses tsc-compatible text output. Indented continuation lines are
+/// appended to the preceding message; summary lines are ignored.
+pub fn parse(output: &str) -> Option<Vec<Diagnostic>> {
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    for line in output.lines() {
+        if let Some(caps) = LOCATED_RE.captures(line) {
+            let num = |a: &str, b: &str| {
+                caps.name(a)
+                    .or_else(|| caps.name(b))
+                    .and_then(|m| m.as_str().parse().ok())
+            };
+            diagnostics.push(Diagnostic {
+                rule: Some(caps["code"].to_string()),
+                severity: Severity::from_label(&caps["sev"]),
+                file: Some(caps["file"].into()),
+                line: num("pl", "cl"),
+                column: num("pc", "cc"),
+                message: caps["msg"].to_string(),
+                ..Default::default()
+            });
+        } else if let Some(caps) = GLOBAL_RE.captures(line) {
+            diagnostics.push(Diagnostic {
+                rule: Some(caps["code"].to_string()),
+                severity: Severity::from_label(&caps["sev"]),
+                message: caps["msg"].to_string(),
+                ..Default::default()
+            });
+        } else if line.starts_with("  ")
+            && let Some(last) = diagnostics.last_mut()
+        {
+            last.message.push(' ');
+            last.message.push_str(line.trim());
+        }
+    }
+
+    if diagnostics.is_empty() && !output.trim().is_empty() {
+        return None;
+    }
+    Some(diagnostics)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
 
     #[test]
     fn skips_without_tsconfig() {
         let info = ProjectInfo {
             root: PathBuf::from("/tmp/nonexistent"),
             has_package_json: true,
-            has_tsconfig: false,
-            has_react: false,
+            ..Default::default()
         };
-        let result = run(&info);
+        let result = run(&ToolContext::new(info));
         assert!(!result.success);
         assert!(result.output.is_empty());
     }
+
+    #[test]
+    fn parses_plain_format_with_continuation() {
+        let output = "src/a.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.\n  Details follow.\n\nFound 1 error.";
+        let diags = parse(output).unwrap();
+        assert_eq!(diags.len(), 1);
+        let d = &diags[0];
+        assert_eq!(d.file.as_deref(), Some(Path::new("src/a.ts")));
+        assert_eq!((d.line, d.column), (Some(3), Some(7)));
+        assert_eq!(d.rule.as_deref(), Some("TS2322"));
+        assert_eq!(d.severity, Severity::Error);
+        assert!(d.message.ends_with("Details follow."));
+    }
+
+    #[test]
+    fn parses_pretty_format() {
+        let output = "src/b.tsx:10:2 - error TS2304: Cannot find name 'foo'.";
+        let diags = parse(output).unwrap();
+        assert_eq!((diags[0].line, diags[0].column), (Some(10), Some(2)));
+        assert_eq!(diags[0].file.as_deref(), Some(Path::new("src/b.tsx")));
+    }
+
+    #[test]
+    fn parses_global_error() {
+        let diags = parse("error TS5083: Cannot read file 'tsconfig.json'.").unwrap();
+        assert!(diags[0].file.is_none());
+        assert_eq!(diags[0].rule.as_deref(), Some("TS5083"));
+    }
+
+    #[test]
+    fn empty_output_is_clean() {
+        assert_eq!(parse("").unwrap().len(), 0);
+    }
+
+    #[test]
+    fn unrecognized_output_falls_back() {
+        assert!(parse("panic: runtime error").is_none());
+    }
 }
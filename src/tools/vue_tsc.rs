@@ -0,0 +1,33 @@
+use super::{ToolContext, ToolResult, tsgo};
+use crate::project::ProjectInfo;
+use crate::resolve;
+
+/// vue-tsc type-checks `.vue` single-file components alongside `.ts`.
+pub fn applies(project: &ProjectInfo) -> bool {
+    project.has_vue && project.has_tsconfig
+}
+
+pub fn run(ctx: &ToolContext) -> ToolResult {
+    if !applies(&ctx.project) {
+        return ToolResult::skipped("vue-tsc");
+    }
+
+    let bin = resolve::resolve_bin("vue-tsc", &ctx.project.root);
+    super::run_command("vue-tsc", &bin, &["--noEmit"], &[], ctx, tsgo::parse)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_vue_and_tsconfig() {
+        let mut project = ProjectInfo {
+            has_vue: true,
+            ..Default::default()
+        };
+        assert!(!applies(&project));
+        project.has_tsconfig = true;
+        assert!(applies(&project));
+    }
+}
@@ -0,0 +1,233 @@
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+
+/// Used when only `nx.json`/`turbo.json` marks the repo as a monorepo.
+const DEFAULT_PATTERNS: &[&str] = &["apps/*", "libs/*", "packages/*"];
+const MAX_GLOB_DEPTH: usize = 8;
+
+/// Collects workspace globs from `pnpm-workspace.yaml`, `package.json`
+/// `workspaces` and `lerna.json`, falling back to the common layout when only
+/// an nx or turbo config is present. Patterns starting with `!` exclude.
+pub fn patterns(root: &Path, package_json: Option<&Value>) -> Vec<String> {
+    let mut patterns = Vec::new();
+
+    if let Ok(yaml) = std::fs::read_to_string(root.join("pnpm-workspace.yaml")) {
+        patterns.extend(pnpm_packages(&yaml));
+    }
+
+    if let Some(workspaces) = package_json.and_then(|p| p.get("workspaces")) {
+        let list = workspaces.get("packages").unwrap_or(workspaces);
+        patterns.extend(string_array(list));
+    }
+
+    if let Ok(content) = std::fs::read_to_string(root.join("lerna.json"))
+        && let Ok(lerna) = serde_json::from_str::<Value>(&content)
+    {
+        match lerna.get("packages") {
+            Some(list) => patterns.extend(string_array(list)),
+            None => patterns.push("packages/*".into()),
+        }
+    }
+
+    if patterns.is_empty() && (root.join("nx.json").exists() || root.join("turbo.json").exists()) {
+        patterns.extend(DEFAULT_PATTERNS.iter().map(|p| p.to_string()));
+    }
+    patterns
+}
+
+fn string_array(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|v| v.as_str())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Reads the `packages:` list of `pnpm-workspace.yaml`, in block or flow
+/// style. Only this key is understood; the rest of the file is ignored.
+fn pnpm_packages(yaml: &str) -> Vec<String> {
+    let unquote = |s: &str| s.trim().trim_matches(|c| c == '\'' || c == '"').to_string();
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let content = line.split(" #").next().unwrap_or_default().trim_end();
+        if content.trim().is_empty() || content.trim_start().starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = false;
+            if let Some(rest) = content.strip_prefix("packages:") {
+                let rest = rest.trim();
+                if let Some(flow) = rest.strip_prefix('[').and_then(|r| r.strip_suffix(']')) {
+                    patterns.extend(flow.split(',').map(unquote).filter(|p| !p.is_empty()));
+                } else {
+                    in_packages = rest.is_empty();
+                }
+            }
+            continue;
+        }
+        if in_packages && let Some(item) = content.trim_start().strip_prefix('-') {
+            patterns.push(unquote(item));
+        }
+    }
+    patterns
+}
+
+/// Expands `patterns` to package directories (those with a `package.json`),
+/// relative to `root`, sorted. Supports `*` within a segment and `**`.
+pub fn expand(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let mut included = Vec::new();
+    let mut excluded = Vec::new();
+    for pattern in patterns {
+        let (negated, pattern) = match pattern.strip_prefix('!') {
+            Some(p) => (true, p),
+            None => (false, pattern.as_str()),
+        };
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        let segments: Vec<&str> = pattern.split('/').filter(|s| !s.is_empty()).collect();
+        let mut matches = Vec::new();
+        walk(root, Path::new(""), &segments, 0, &mut matches);
+        if negated {
+            excluded.extend(matches);
+        } else {
+            included.extend(matches);
+        }
+    }
+    included.retain(|dir| !excluded.contains(dir) && root.join(dir).join("package.json").is_file());
+    included.sort();
+    included.dedup();
+    included
+}
+
+fn walk(root: &Path, rel: &Path, segments: &[&str], depth: usize, out: &mut Vec<PathBuf>) {
+    let Some((first, rest)) = segments.split_first() else {
+        if !rel.as_os_str().is_empty() {
+            out.push(rel.to_path_buf());
+        }
+        return;
+    };
+    if depth > MAX_GLOB_DEPTH {
+        return;
+    }
+    if !first.contains('*') {
+        let next = rel.join(first);
+        if root.join(&next).is_dir() {
+            walk(root, &next, rest, depth + 1, out);
+        }
+        return;
+    }
+    if *first == "**" {
+        walk(root, rel, rest, depth + 1, out);
+    }
+    let Ok(entries) = std::fs::read_dir(root.join(rel)) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else { continue };
+        if name.starts_with('.') || name == "node_modules" || !entry.path().is_dir() {
+            continue;
+        }
+        if *first == "**" {
+            walk(root, &rel.join(name), segments, depth + 1, out);
+        } else if wildcard_match(first, name) {
+            walk(root, &rel.join(name), rest, depth + 1, out);
+        }
+    }
+}
+
+/// Matches a single path segment against a pattern where `*` matches any
+/// run of characters.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let (first, last) = (parts[0], parts[parts.len() - 1]);
+    if !name.starts_with(first) || name.len() < first.len() + last.len() {
+        return false;
+    }
+    let mut rest = &name[first.len()..];
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(i) => rest = &rest[i + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TempDir;
+    use std::fs;
+
+    fn package(root: &Path, dir: &str) {
+        fs::create_dir_all(root.join(dir)).unwrap();
+        fs::write(root.join(dir).join("package.json"), "{}").unwrap();
+    }
+
+    #[test]
+    fn parses_pnpm_block_and_flow_lists() {
+        let block = "packages:\n  - 'apps/*'\n  - \"packages/**\" # all\n  - '!**/test/**'\ncatalog:\n  - nope\n";
+        assert_eq!(
+            pnpm_packages(block),
+            vec!["apps/*", "packages/**", "!**/test/**"]
+        );
+        assert_eq!(
+            pnpm_packages("packages: ['apps/*', \"libs/*\"]\n"),
+            vec!["apps/*", "libs/*"]
+        );
+    }
+
+    #[test]
+    fn reads_package_json_and_lerna_patterns() {
+        let tmp = TempDir::new("workspace-patterns");
+        let pkg: Value =
+            serde_json::from_str(r#"{"workspaces": {"packages": ["apps/*"]}}"#).unwrap();
+        fs::write(tmp.join("lerna.json"), r#"{"version": "1.0.0"}"#).unwrap();
+        assert_eq!(patterns(&tmp, Some(&pkg)), vec!["apps/*", "packages/*"]);
+    }
+
+    #[test]
+    fn nx_without_patterns_uses_defaults() {
+        let tmp = TempDir::new("workspace-nx");
+        fs::write(tmp.join("nx.json"), "{}").unwrap();
+        assert_eq!(patterns(&tmp, None), DEFAULT_PATTERNS);
+        assert!(patterns(&TempDir::new("workspace-none"), None).is_empty());
+    }
+
+    #[test]
+    fn expands_globs_to_package_dirs() {
+        let tmp = TempDir::new("workspace-expand");
+        package(&tmp, "apps/web");
+        package(&tmp, "apps/docs");
+        package(&tmp, "packages/ui/button");
+        package(&tmp, "packages/ui/test/fixture");
+        package(&tmp, "packages/node_modules/dep");
+        fs::create_dir_all(tmp.join("apps/no-manifest")).unwrap();
+
+        let dirs = expand(
+            &tmp,
+            &[
+                "apps/*".into(),
+                "./packages/**".into(),
+                "!**/test/**".into(),
+            ],
+        );
+        let dirs: Vec<_> = dirs.iter().map(|d| d.to_str().unwrap()).collect();
+        assert_eq!(dirs, vec!["apps/docs", "apps/web", "packages/ui/button"]);
+    }
+
+    #[test]
+    fn wildcard_within_segment() {
+        assert!(wildcard_match("pkg-*", "pkg-a"));
+        assert!(wildcard_match("*-app", "web-app"));
+        assert!(wildcard_match("a*b*c", "axxbyyc"));
+        assert!(!wildcard_match("pkg-*", "lib-a"));
+        assert!(!wildcard_match("a*a", "a"));
+    }
+}
@@ -35,6 +35,7 @@ impl Drop for TempDir {
 fn run_reviews_in(dir: &std::path::Path, input: &str) -> (String, String, bool) {
     let mut child = Command::new(env!("CARGO_BIN_EXE_reviews"))
         .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir.join(".xdg"))
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -56,6 +57,21 @@ fn run_reviews_in(dir: &std::path::Path, input: &str) -> (String, String, bool)
     )
 }
 
+fn run_reviews_args(dir: &std::path::Path, args: &[&str]) -> (String, String, Option<i32>) {
+    let output = Command::new(env!("CARGO_BIN_EXE_reviews"))
+        .args(args)
+        .current_dir(dir)
+        .env("XDG_CONFIG_HOME", dir.join(".xdg"))
+        .stdin(Stdio::null())
+        .output()
+        .expect("failed to spawn reviews");
+    (
+        String::from_utf8_lossy(&output.stdout).to_string(),
+        String::from_utf8_lossy(&output.stderr).to_string(),
+        output.status.code(),
+    )
+}
+
 fn run_reviews(input: &str) -> (String, String, bool) {
     let tmp = TempDir::new("default");
     std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
@@ -112,7 +128,9 @@ fn default_skill_does_not_crash() {
     if !stdout.is_empty() {
         let parsed: serde_json::Value = serde_json::from_str(&stdout)
             .unwrap_or_else(|e| panic!("invalid JSON output: {e}\nstdout: {stdout}"));
-        assert_eq!(parsed["decision"], "approve");
+        let output = &parsed["hookSpecificOutput"];
+        assert_eq!(output["hookEventName"], "PreToolUse");
+        assert_eq!(output["permissionDecision"], "allow");
     }
 }
 
@@ -122,7 +140,7 @@ fn configured_skill_does_not_crash() {
     std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
     std::fs::write(
         tmp.path().join(".claude-reviews.json"),
-        r#"{"skills": ["audit"]}"#,
+        r#"{"skills": ["audit"], "legacy_output": true}"#,
     )
     .unwrap();
 
@@ -142,3 +160,113 @@ fn exits_zero_on_valid_processing() {
     let (_, _, success) = run_reviews(r#"{"tool_name": "Skill", "tool_input": {}}"#);
     assert!(success, "should exit 0 even when skill field is missing");
 }
+
+#[test]
+fn baseline_command_writes_file() {
+    let tmp = TempDir::new("baseline");
+    std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+    std::fs::write(
+        tmp.path().join(".claude-reviews.json"),
+        r#"{"tools": {"knip": false, "oxlint": false, "tsgo": false, "react_doctor": false}}"#,
+    )
+    .unwrap();
+
+    let (stdout, _, code) = run_reviews_args(tmp.path(), &["baseline"]);
+    assert_eq!(code, Some(0));
+    assert!(stdout.contains("wrote 0 findings"));
+
+    let written =
+        std::fs::read_to_string(tmp.path().join(".claude-reviews-baseline.json")).unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&written).unwrap();
+    assert_eq!(parsed["findings"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn unknown_command_exits_with_usage() {
+    let tmp = TempDir::new("unknown-cmd");
+    let (_, stderr, code) = run_reviews_args(tmp.path(), &["frobnicate"]);
+    assert_eq!(code, Some(2));
+    assert!(stderr.contains("usage:"));
+}
+
+#[test]
+fn run_command_prints_json_without_stdin() {
+    let tmp = TempDir::new("run-json");
+    std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+    std::fs::write(
+        tmp.path().join(".claude-reviews.json"),
+        r#"{"enabled": false, "tools": {"knip": false, "oxlint": false, "tsgo": false, "react_doctor": false, "vue_tsc": false, "svelte_check": false, "astro_check": false, "eslint": false, "biome": false, "clippy": false, "cargo_check": false, "machete": false, "audit": false, "ruff": false, "mypy": false, "pyright": false, "go_vet": false, "staticcheck": false, "golangci_lint": false}}"#,
+    )
+    .unwrap();
+
+    let (stdout, _, code) = run_reviews_args(tmp.path(), &["run", "--format", "json"]);
+    assert_eq!(code, Some(0));
+    let parsed: serde_json::Value = serde_json::from_str(&stdout)
+        .unwrap_or_else(|e| panic!("invalid JSON output: {e}\nstdout: {stdout}"));
+    assert_eq!(parsed["tools"].as_array().unwrap().len(), 0);
+}
+
+#[test]
+fn doctor_passes_when_all_tools_disabled() {
+    let tmp = TempDir::new("doctor");
+    std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+    std::fs::write(
+        tmp.path().join(".claude-reviews.json"),
+        r#"{"tools": {"knip": false, "oxlint": false, "tsgo": false, "react_doctor": false}}"#,
+    )
+    .unwrap();
+
+    let (stdout, _, code) = run_reviews_args(tmp.path(), &["doctor"]);
+    assert_eq!(code, Some(0));
+    assert!(stdout.contains("project: "));
+    assert!(stdout.contains("tools:"));
+}
+
+#[test]
+fn custom_tool_runs_alongside_builtins() {
+    let tmp = TempDir::new("custom-tool");
+    std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+    std::fs::write(
+        tmp.path().join(".claude-reviews.json"),
+        r#"{
+            "tools": {"knip": false, "oxlint": false, "tsgo": false, "react_doctor": false, "vue_tsc": false, "svelte_check": false, "astro_check": false, "eslint": false, "biome": false, "clippy": false, "cargo_check": false, "machete": false, "audit": false, "ruff": false, "mypy": false, "pyright": false, "go_vet": false, "staticcheck": false, "golangci_lint": false},
+            "custom_tools": [
+                {"name": "codemod-check", "command": "sh", "args": ["-c", "echo stale codemod"]},
+                {"name": "never", "command": "sh", "when": {"files": ["missing.cfg"]}}
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let (stdout, _, code) = run_reviews_args(tmp.path(), &["run", "--format", "json"]);
+    assert_eq!(code, Some(0));
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let tools = parsed["tools"].as_array().unwrap();
+    assert_eq!(tools.len(), 2);
+    assert_eq!(tools[0]["name"], "codemod-check");
+    assert_eq!(tools[0]["output"], "stale codemod");
+    assert_eq!(tools[1]["output"], "");
+}
+
+#[test]
+fn doctor_reports_user_config_layer() {
+    let tmp = TempDir::new("doctor-layers");
+    std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+    let user_dir = tmp.path().join(".xdg/claude-reviews");
+    std::fs::create_dir_all(&user_dir).unwrap();
+    std::fs::write(
+        user_dir.join("config.json"),
+        r#"{"tools": {"knip": false, "oxlint": false, "tsgo": false, "react_doctor": false}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        tmp.path().join(".claude-reviews.local.json"),
+        r#"{"skills": ["audit"]}"#,
+    )
+    .unwrap();
+
+    let (stdout, _, code) = run_reviews_args(tmp.path(), &["doctor"]);
+    assert_eq!(code, Some(0), "{stdout}");
+    assert!(stdout.contains("tools.knip set by user"), "{stdout}");
+    assert!(stdout.contains("skills set by local"), "{stdout}");
+}